@@ -0,0 +1,45 @@
+//! # Mock Oracle
+//!
+//! A price oracle for the end-to-end tests of The Button.
+//! It quotes how many native units one unit of the reference currency is worth,
+//! together with the timestamp of the quote. Anyone can change the quote.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(unexpected_cfgs)]
+
+pub use self::mock_oracle::{MockOracle, MockOracleRef};
+
+#[ink::contract]
+mod mock_oracle {
+    #[ink(storage)]
+    pub struct MockOracle {
+        /// The native units per unit of the reference currency
+        rate: Balance,
+        /// The timestamp of the quote
+        quoted_at: u64,
+    }
+
+    impl MockOracle {
+        /// Quotes `rate` as of the block timestamp of the construction.
+        #[ink(constructor)]
+        pub fn new(rate: Balance) -> Self {
+            Self {
+                rate,
+                quoted_at: Self::env().block_timestamp(),
+            }
+        }
+
+        /// Quotes `rate` as of the timestamp `quoted_at`, which can be in the past to simulate a stale quote.
+        #[ink(message)]
+        pub fn set_rate(&mut self, rate: Balance, quoted_at: u64) {
+            self.rate = rate;
+            self.quoted_at = quoted_at;
+        }
+
+        /// Return the quoted rate and the timestamp of the quote
+        #[ink(message)]
+        pub fn get_rate(&self) -> (Balance, u64) {
+            (self.rate, self.quoted_at)
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! # Mock Payout Receiver
+//!
+//! An `on_payout_callback` contract for the end-to-end tests of The Button.
+//! It records the last payout it was notified of, or burns all the gas it is given
+//! to simulate a misbehaving callback.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(unexpected_cfgs)]
+
+pub use self::mock_payout_receiver::{MockPayoutReceiver, MockPayoutReceiverRef};
+
+#[ink::contract]
+mod mock_payout_receiver {
+    #[ink(storage)]
+    pub struct MockPayoutReceiver {
+        /// Whether notifications loop until they run out of gas
+        burn_gas: bool,
+        /// The winner and the amount of the last payout
+        last_payout: Option<(AccountId, Balance)>,
+        /// How many iterations the last gas-burning notification ran for
+        iterations: u64,
+    }
+
+    impl MockPayoutReceiver {
+        /// Creates a receiver that records payouts, or burns all its gas if `burn_gas` is set.
+        #[ink(constructor)]
+        pub fn new(burn_gas: bool) -> Self {
+            Self {
+                burn_gas,
+                last_payout: None,
+                iterations: 0,
+            }
+        }
+
+        /// Called by The Button after a payout
+        #[ink(message)]
+        pub fn on_button_payout(&mut self, winner: AccountId, amount: Balance) {
+            while self.burn_gas {
+                // the host call keeps the loop from being optimized away
+                self.iterations = self.iterations.wrapping_add(u64::from(self.env().block_number()));
+            }
+            self.last_payout = Some((winner, amount));
+        }
+
+        /// Return the winner and the amount of the last payout, if any
+        #[ink(message)]
+        pub fn get_last_payout(&self) -> Option<(AccountId, Balance)> {
+            self.last_payout
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! # Mock Router
+//!
+//! A `stable_router` for the end-to-end tests of The Button.
+//! Instead of swapping, it forwards the native value to the recipient and records the swap.
+//! It can be told to reject swaps to exercise the native fallback.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(unexpected_cfgs)]
+
+pub use self::mock_router::{MockRouter, MockRouterRef};
+
+#[ink::contract]
+mod mock_router {
+    #[ink(storage)]
+    pub struct MockRouter {
+        /// Whether swaps are rejected
+        reject_swaps: bool,
+        /// The recipient and the amount of the last swap
+        last_swap: Option<(AccountId, Balance)>,
+    }
+
+    impl MockRouter {
+        /// Creates a router that forwards swaps, or rejects them if `reject_swaps` is set.
+        #[ink(constructor)]
+        pub fn new(reject_swaps: bool) -> Self {
+            Self {
+                reject_swaps,
+                last_swap: None,
+            }
+        }
+
+        /// Forwards the transferred value to `recipient` as if it had been swapped.
+        /// Reverts if swaps are rejected.
+        #[ink(message, payable)]
+        pub fn swap_to_stable(&mut self, recipient: AccountId) {
+            assert!(!self.reject_swaps, "swaps are rejected");
+            let amount = self.env().transferred_value();
+            self.env().transfer(recipient, amount).expect("forwarding the swap failed");
+            self.last_swap = Some((recipient, amount));
+        }
+
+        /// Return the recipient and the amount of the last swap, if any
+        #[ink(message)]
+        pub fn get_last_swap(&self) -> Option<(AccountId, Balance)> {
+            self.last_swap
+        }
+    }
+}
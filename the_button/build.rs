@@ -0,0 +1,11 @@
+//! ink_e2e builds the contract without any crate features, so the end-to-end tests
+//! cannot reach `test-helpers` through `e2e-tests`. Setting `THE_BUTTON_TEST_HELPERS`
+//! turns the feature on for every build of the contract, including the one ink_e2e runs:
+//!
+//! `THE_BUTTON_TEST_HELPERS=1 cargo test --features e2e-tests`
+fn main() {
+    println!("cargo:rerun-if-env-changed=THE_BUTTON_TEST_HELPERS");
+    if std::env::var_os("THE_BUTTON_TEST_HELPERS").is_some() {
+        println!("cargo:rustc-cfg=feature=\"test-helpers\"");
+    }
+}
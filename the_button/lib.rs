@@ -11,17 +11,78 @@
 //!
 //! ## Details
 //! The contract is initialized with the countdown duration in milliseconds.
+//! On chains where timestamps are unreliable the countdown can be based on block numbers instead.
 //! Users can press the button by calling the `press()` function and paying `min_raise_balance`.
+//! The price they are willing to pay is passed along, so that a price change cannot make them overpay.
 //! This resets the countdown.
 //! If `countdown_duration` has passed since the last press, any user can claim the reward for the winner
 //! by calling the `payout()` function.
 //! The game ends when the reward is claimed and the contract will self-destruct.
+//! In perpetual mode the contract stays alive instead and a new round starts after each payout.
+//! The contract can additionally host independent games created with `create_game()`.
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 #![allow(unexpected_cfgs)]
+// The generated constructor dispatch enum carries the whole `GameConfig` of `with_config`.
+#![allow(clippy::large_enum_variant)]
 
 #[ink::contract]
 mod the_button {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::storage::Mapping;
+
+    /// The version of the contract's behaviour. Bumped with every release that adds or changes features,
+    /// so a client can compare it against the version a feature was introduced in.
+    pub const CONTRACT_VERSION: u32 = 2;
+
+    /// Basis points representing 100%.
+    const BPS_DENOMINATOR: u16 = 10_000;
+
+    /// Maximum length of a round theme in bytes.
+    const MAX_ROUND_THEME_LEN: usize = 64;
+
+    /// Maximum number of payout approvers.
+    const MAX_APPROVERS: usize = 16;
+
+    /// Maximum number of accounts in a batch query.
+    const MAX_BATCH_ACCOUNTS: usize = 64;
+
+    /// Maximum number of accounts on the leaderboard.
+    const MAX_LEADERBOARD_SIZE: u32 = 32;
+
+    /// Maximum number of recipients a winner can split their winnings between.
+    const MAX_SPLIT_RECIPIENTS: usize = 8;
+
+    /// Maximum number of jackpot tiers.
+    const MAX_JACKPOT_TIERS: usize = 16;
+
+    /// Maximum number of recent pressers that receive a consolation prize.
+    const MAX_CONSOLATION_RECIPIENTS: u32 = 8;
+
+    /// Maximum number of additional games running at the same time.
+    const MAX_ACTIVE_GAMES: usize = 32;
+
+    /// Maximum length of a press message in bytes.
+    const MAX_PRESS_MESSAGE_LEN: usize = 64;
+
+    /// Maximum number of press timestamps kept for `get_recent_press_times()`.
+    const MAX_PRESS_HISTORY: u32 = 32;
+
+    /// Maximum number of presses projected by `get_price_after()`.
+    const MAX_PRICE_PROJECTION: u32 = 256;
+
+    /// Maximum number of pot thresholds that extend the countdown.
+    const MAX_POT_EXTENSIONS: usize = 16;
+
+    /// Maximum number of banned accounts.
+    const MAX_BANNED_ACCOUNTS: usize = 256;
+
+    /// Reference time the `on_payout_callback` may use, so that a callback burning its gas cannot block the payout.
+    const PAYOUT_CALLBACK_REF_TIME_LIMIT: u64 = 5_000_000_000;
+
+    /// Proof size the `on_payout_callback` may use.
+    const PAYOUT_CALLBACK_PROOF_SIZE_LIMIT: u64 = 256 * 1024;
 
     #[ink(storage)]
     pub struct TheButton {
@@ -29,10 +90,510 @@ mod the_button {
         last_press_caller: AccountId,
         /// The timestamp of the last call
         last_press_timestamp: u64,
+        /// The game parameters
+        config: GameConfig,
+        /// The account receiving the house fees
+        beneficiary: AccountId,
+        /// House fees collected from presses and not yet paid out
+        accrued_fees: Balance,
+        /// The account that created the contract
+        owner: AccountId,
+        /// How often the button has been pressed
+        press_count: u64,
+        /// The theme of the current round
+        round_theme: String,
+        /// The theme scheduled for the next round
+        next_round_theme: Option<String>,
+        /// The amount the last press added to the pot in the current round
+        last_press_amount: Balance,
+        /// The approvers that approved paying out the current leader
+        payout_approvals: Vec<AccountId>,
+        /// The block number of the last call
+        last_press_block: BlockNumber,
+        /// The accounts that have pressed the button, keyed by round
+        players: Mapping<(u32, AccountId), ()>,
+        /// How many different accounts have pressed the button in the current round
+        unique_player_count: u32,
+        /// The accounts that want their winnings swapped to the stable token
+        stable_payout: Mapping<AccountId, ()>,
+        /// How often each account lost the lead to another account
+        times_dethroned: Mapping<AccountId, u64>,
+        /// The anti-sniping extension added to the current countdown
+        countdown_extension: u64,
+        /// The timestamp at which the owner froze the countdown, if it is frozen
+        frozen_at: Option<u64>,
+        /// The milliseconds the current countdown was frozen before the latest resume
+        total_frozen: u64,
+        /// How many of the `pot_extensions` have extended the countdown in the current round
+        pot_extensions_fired: u32,
+        /// How often each account has pressed the button
+        press_counts: Mapping<(u32, AccountId), u64>,
+        /// How often the button has been pressed in the current round
+        round_press_count: u64,
+        /// The accounts with the most presses and their press counts, sorted descending
+        top_pressers: Vec<(AccountId, u64)>,
+        /// Where each account wants its winnings to be sent
+        payout_destination: Mapping<AccountId, AccountId>,
+        /// The accounts allowed to press while the whitelist is enabled
+        whitelist: Mapping<AccountId, ()>,
+        /// Whether only whitelisted accounts can press the button
+        whitelist_enabled: bool,
+        /// The accounts banned from pressing the button
+        blacklist: Mapping<AccountId, ()>,
+        /// The banned accounts in the order they were banned, kept in sync with `blacklist`
+        banned_accounts: Vec<AccountId>,
+        /// The last distinct pressers of the round, most recent first
+        recent_pressers: Vec<AccountId>,
+        /// The additional games hosted by the contract, by game id
+        games: Mapping<u32, ButtonGame>,
+        /// The id of the next additional game
+        next_game_id: u32,
+        /// The sum of the pots of the additional games, which is not part of the main game's pot
+        games_pot: Balance,
+        /// The ids of the additional games that have not been paid out yet
+        active_games: Vec<u32>,
+        /// The sum of the amounts all presses ever added to the pot
+        total_volume: Balance,
+        /// The message left by the last press
+        last_press_message: Vec<u8>,
+        /// The endowment of the creator that is refunded on the next payout
+        creator_seed: Balance,
+        /// The account that deployed the contract. It keeps the refund of its seed after an ownership transfer.
+        creator: AccountId,
+        /// The timestamps of the latest presses, oldest first
+        recent_press_times: Vec<u64>,
+        /// The lifetime winnings of each account
+        total_won: Mapping<AccountId, Balance>,
+        /// The lifetime amount each account has raised the pot by
+        contributed: Mapping<AccountId, Balance>,
+        /// The accounts allowed to press on behalf of other accounts
+        relayers: Mapping<AccountId, ()>,
+        /// The timestamp of the construction
+        created_at: u64,
+        /// The winnings credited to each account in pull payment mode
+        withdrawable: Mapping<AccountId, Balance>,
+        /// The sum of all credited winnings, which is not part of the pot
+        total_withdrawable: Balance,
+        /// The countdown duration scheduled to apply from the next press
+        next_countdown_duration: Option<u64>,
+        /// The account that was the last caller before the current one
+        runner_up: AccountId,
+        /// The commitments of the accounts that want to press with `reveal()`
+        commitments: Mapping<AccountId, Hash>,
+        /// The winner of the latest payout
+        last_winner: Option<AccountId>,
+        /// The timestamp of the latest payout
+        last_payout_timestamp: u64,
+        /// The gas a press is advised to be given, as set by the owner
+        gas_hint: u64,
+        /// The season of the statistics. `reset_stats()` starts a new one, in which every account has zero presses.
+        stats_season: u32,
+        /// The press count at the start of the season of the statistics
+        stats_press_count_base: u64,
+        /// The last rate quoted by the price oracle, in native units per unit of the reference currency
+        oracle_rate: Balance,
+        /// The timestamp of the last quote of the price oracle
+        oracle_quoted_at: u64,
+        /// The number of the current round, starting at 1
+        round: u32,
+        /// Replaces the on-chain entropy of the jackpot roll. Only set by tests.
+        #[cfg(test)]
+        jackpot_seed_override: Option<[u8; 32]>,
+    }
+
+    /// Who may call `payout()`.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PayoutAccess {
+        /// Any account can trigger the payout
+        #[default]
+        Anyone,
+        /// Only the winner can trigger the payout
+        WinnerOnly,
+        /// Only the owner can trigger the payout
+        OwnerOnly,
+    }
+
+    /// The status of the game, as returned by `get_status()`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum GameStatus {
+        /// The start time has not been reached yet
+        NotStarted,
+        /// The countdown is running or the payout is not allowed yet
+        Active,
+        /// The round can be paid out
+        PayoutReady,
+        /// The maximum lifetime has passed and the game cannot be paid out
+        Ended,
+        /// The owner has frozen the countdown
+        Paused,
+    }
+
+    /// The parameters of a game.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct GameConfig {
+        /// How long the countdown is
+        pub countdown_duration: u64,
+        /// Minimum balance a press has to add to the pot, after fees
+        pub min_raise_balance: Balance,
+        /// Share of every press kept as a house fee, in basis points
+        pub fee_bps: u16,
+        /// The account receiving the house fees. Defaults to the contract creator.
+        pub beneficiary: Option<AccountId>,
+        /// Whether a new round starts after a payout instead of terminating the contract
+        pub perpetual: bool,
+        /// How much each press has to add on top of the previous press. Zero disables the rule.
+        pub min_increment: Balance,
+        /// Share of the pot paid to the winner, in basis points.
+        /// The remainder seeds the next round and requires perpetual mode.
+        pub winner_share_bps: u16,
+        /// The accounts allowed to approve a payout
+        pub approvers: Vec<AccountId>,
+        /// How many approvers have to approve a payout. Zero disables approvals.
+        pub approval_threshold: u32,
+        /// Whether the countdown is measured in blocks instead of milliseconds
+        pub use_block_number: bool,
+        /// How many blocks the countdown lasts when measured in blocks
+        pub countdown_blocks: BlockNumber,
+        /// How many different accounts have to press the button in a round before its payout is allowed
+        pub min_unique_players: u32,
+        /// The router swapping winnings to the stable token for winners that opted in.
+        /// It has to implement a payable `swap_to_stable(recipient: AccountId)` message.
+        pub stable_router: Option<AccountId>,
+        /// A press this close to the deadline extends the countdown by `anti_snipe_extension`.
+        /// Both are in the unit of the countdown. Zero disables the extension.
+        pub anti_snipe_window: u64,
+        /// How much a press within `anti_snipe_window` extends the countdown
+        pub anti_snipe_extension: u64,
+        /// How many blocks the last press has to be held before a payout is allowed
+        pub min_hold_blocks: BlockNumber,
+        /// How many milliseconds an account has to wait before pressing again
+        /// while it still holds the button
+        pub self_press_cooldown: u64,
+        /// How many accounts are kept on the leaderboard
+        pub leaderboard_size: u32,
+        /// How long after the deadline a press can still revive the round, in the unit of the countdown.
+        /// Later presses are rejected in favor of the payout. `None` allows presses at any time.
+        pub reopen_window: Option<u64>,
+        /// The chain's existential deposit, which a payout always leaves in the contract
+        pub existential_deposit: Balance,
+        /// The advertised jackpot tiers as `(press_threshold, bonus)`, sorted by strictly increasing threshold
+        pub jackpot_tiers: Vec<(u64, Balance)>,
+        /// How many milliseconds have to pass between any two presses
+        pub min_press_interval: u64,
+        /// How many of the last distinct pressers before the winner receive a consolation prize
+        pub consolation_count: u32,
+        /// The share of the pot split evenly between the consolation recipients, in basis points.
+        /// It is taken from the winner's share.
+        pub consolation_bps: u16,
+        /// The cap for the escalating price of a press. Zero disables the cap.
+        pub max_price: Balance,
+        /// Who may call `payout()`
+        pub payout_access: PayoutAccess,
+        /// A flat fee per press that is forwarded to the beneficiary right away
+        pub press_fee: Balance,
+        /// How long after the deadline an unclaimed game counts as abandoned, so that the owner
+        /// can withdraw its balance. `None` disables emergency withdrawals.
+        pub abandonment_grace: Option<u64>,
+        /// How many milliseconds each press takes off the countdown duration. Zero keeps it fixed.
+        pub countdown_step: u64,
+        /// The shortest countdown duration that `countdown_step` can reduce it to
+        pub countdown_floor: u64,
+        /// Whether the endowment paid to `with_config` is refunded to the creator on the first payout
+        /// instead of becoming part of the pot
+        pub refund_creator_seed: bool,
+        /// A contract that is notified of each payout through its `on_button_payout(winner, amount)` message
+        pub on_payout_callback: Option<AccountId>,
+        /// How many of the latest press timestamps are kept
+        pub press_history_size: u32,
+        /// Whether only the first press of a block counts if several presses share the block and timestamp
+        pub first_press_per_block_wins: bool,
+        /// The smallest pot that can be paid out
+        pub min_payout_pot: Balance,
+        /// The account that receives `charity_bps` of every pot
+        pub charity: Option<AccountId>,
+        /// The share of the pot donated to `charity`, in basis points. It is taken from the winner's share.
+        pub charity_bps: u16,
+        /// The minimum the current leader has to pay to reset the countdown with `top_up()`
+        pub top_up_min: Balance,
+        /// How many milliseconds after the construction the game ends for good. Zero disables the limit.
+        pub max_lifetime: u64,
+        /// Whether winnings are credited to be collected with `withdraw()` instead of being transferred.
+        /// Requires perpetual mode, as terminating the contract would take the credited winnings with it.
+        pub pull_payments: bool,
+        /// Opts in to the random jackpot bonus. The roll is derived from the block number, the timestamp
+        /// and the state of the round, which are all public: anyone can predict it by dry-running `payout()`
+        /// and trigger the payout only in a block that wins, and block producers can pick the outcome.
+        /// It is a game mechanic, not a source of randomness, so it must not guard meaningful amounts.
+        pub random_jackpot: bool,
+        /// The chance in basis points that a payout adds the accrued house fees to the winner's prize.
+        /// Requires `random_jackpot`.
+        pub jackpot_chance_bps: u16,
+        /// The decimals of the chain's native token, for front-ends to format balances
+        pub decimals: u8,
+        /// The timestamp before which the button cannot be pressed. The first countdown starts from it.
+        /// Zero lets the game start right away.
+        pub start_time: u64,
+        /// The most a single press can transfer. Zero disables the cap.
+        pub max_raise_balance: Balance,
+        /// The shares of the winner's prize for the winner, the runner-up and the third-to-last distinct presser,
+        /// in basis points adding up to 100%. The shares of missing pressers go to the winner. `None` pays everything to the winner.
+        pub podium_bps: Option<(u16, u16, u16)>,
+        /// Whether a payout keeps the accrued house fees in the contract to seed the next round
+        /// instead of paying them to the beneficiary. Requires perpetual mode.
+        pub reseed_fees: bool,
+        /// Whether presses have to be committed with `commit()` and made with `reveal()`,
+        /// so that the intent to press cannot be front-run
+        pub commit_reveal: bool,
+        /// How many milliseconds after a payout its winner cannot press again
+        pub winner_reentry_cooldown: u64,
+        /// The share of the pot paid to the account that triggers the payout, in basis points.
+        /// It is taken from the winner's share.
+        pub payout_bounty_bps: u16,
+        /// A contract quoting the native units per unit of a reference currency through its `get_rate()` message,
+        /// which returns the rate and the timestamp of the quote. If set, the minimum raise is at least
+        /// `reference_min_raise` converted at the quoted rate, capped at `max_price`.
+        pub price_oracle: Option<AccountId>,
+        /// The minimum raise in units of the reference currency of the `price_oracle`
+        pub reference_min_raise: Balance,
+        /// How many milliseconds old a quote of the `price_oracle` can be before presses are rejected.
+        /// Has to be non-zero if there is a price oracle.
+        pub max_quote_age: u64,
+        /// How many presses a round allows. Once reached, the round can be paid out right away. Zero disables the limit.
+        pub max_presses_per_round: u64,
+        /// The countdown extensions as `(pot_threshold, extra_duration)`, sorted by strictly increasing threshold.
+        /// The first press of a round that raises the pot to a threshold extends its countdown by the extra duration,
+        /// in the unit of the countdown.
+        pub pot_extensions: Vec<(Balance, u64)>,
+    }
+
+    impl GameConfig {
+        /// Creates a config with the given countdown and minimum raise and no house fee.
+        pub fn new(countdown_duration: u64, min_raise_balance: Balance) -> Self {
+            Self {
+                countdown_duration,
+                min_raise_balance,
+                fee_bps: 0,
+                beneficiary: None,
+                perpetual: false,
+                min_increment: 0,
+                winner_share_bps: BPS_DENOMINATOR,
+                approvers: Vec::new(),
+                approval_threshold: 0,
+                use_block_number: false,
+                countdown_blocks: 0,
+                min_unique_players: 0,
+                stable_router: None,
+                anti_snipe_window: 0,
+                anti_snipe_extension: 0,
+                min_hold_blocks: 0,
+                self_press_cooldown: 0,
+                leaderboard_size: 0,
+                reopen_window: None,
+                existential_deposit: 0,
+                jackpot_tiers: Vec::new(),
+                min_press_interval: 0,
+                consolation_count: 0,
+                consolation_bps: 0,
+                max_price: 0,
+                payout_access: PayoutAccess::Anyone,
+                press_fee: 0,
+                abandonment_grace: None,
+                countdown_step: 0,
+                countdown_floor: 0,
+                refund_creator_seed: false,
+                on_payout_callback: None,
+                press_history_size: 0,
+                first_press_per_block_wins: false,
+                min_payout_pot: 0,
+                charity: None,
+                charity_bps: 0,
+                top_up_min: 0,
+                max_lifetime: 0,
+                pull_payments: false,
+                random_jackpot: false,
+                jackpot_chance_bps: 0,
+                decimals: 12,
+                start_time: 0,
+                max_raise_balance: 0,
+                podium_bps: None,
+                reseed_fees: false,
+                commit_reveal: false,
+                winner_reentry_cooldown: 0,
+                payout_bounty_bps: 0,
+                price_oracle: None,
+                reference_min_raise: 0,
+                max_quote_age: 0,
+                max_presses_per_round: 0,
+                pot_extensions: Vec::new(),
+            }
+        }
+
+        /// Checks that the parameters are consistent.
+        fn validate(&self) -> Result<()> {
+            if self.fee_bps >= BPS_DENOMINATOR {
+                return Err(Error::InvalidConfig);
+            }
+            if self.winner_share_bps > BPS_DENOMINATOR
+                || (self.winner_share_bps < BPS_DENOMINATOR && !self.perpetual)
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if self.approvers.len() > MAX_APPROVERS
+                || self.approval_threshold as usize > self.approvers.len()
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if self.leaderboard_size > MAX_LEADERBOARD_SIZE {
+                return Err(Error::InvalidConfig);
+            }
+            if self.jackpot_tiers.len() > MAX_JACKPOT_TIERS
+                || self.jackpot_tiers.windows(2).any(|tiers| tiers[0].0 >= tiers[1].0)
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if self.pot_extensions.len() > MAX_POT_EXTENSIONS
+                || self.pot_extensions.windows(2).any(|extensions| extensions[0].0 >= extensions[1].0)
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if self.consolation_count > MAX_CONSOLATION_RECIPIENTS
+                || u32::from(self.consolation_bps)
+                    .saturating_add(u32::from(self.charity_bps))
+                    .saturating_add(u32::from(self.payout_bounty_bps))
+                    > u32::from(self.winner_share_bps)
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if self.charity_bps > 0 && self.charity.is_none() {
+                return Err(Error::InvalidConfig);
+            }
+            if self.max_price != 0 && self.max_price < self.min_raise_balance {
+                return Err(Error::InvalidConfig);
+            }
+            if self.max_raise_balance != 0 && self.max_raise_balance < self.min_raise_balance {
+                return Err(Error::InvalidConfig);
+            }
+            if let Some((first, second, third)) = self.podium_bps {
+                if u32::from(first).saturating_add(u32::from(second)).saturating_add(u32::from(third)) != u32::from(BPS_DENOMINATOR) {
+                    return Err(Error::InvalidConfig);
+                }
+            }
+            if self.countdown_step > 0 && self.countdown_floor > self.countdown_duration {
+                return Err(Error::InvalidConfig);
+            }
+            if self.press_history_size > MAX_PRESS_HISTORY {
+                return Err(Error::InvalidConfig);
+            }
+            if (self.pull_payments || self.reseed_fees) && !self.perpetual {
+                return Err(Error::InvalidConfig);
+            }
+            if self.jackpot_chance_bps > BPS_DENOMINATOR || (self.jackpot_chance_bps > 0 && !self.random_jackpot) {
+                return Err(Error::InvalidConfig);
+            }
+            // a zero quote age would reject every press that does not share the block of the quote
+            if self.price_oracle.is_some() && self.max_quote_age == 0 {
+                return Err(Error::InvalidConfig);
+            }
+            Ok(())
+        }
+    }
+
+    /// An additional, independent game hosted by the contract.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ButtonGame {
+        /// The account of the last caller
+        pub last_press_caller: AccountId,
+        /// The timestamp of the last press
+        pub last_press_timestamp: u64,
+        /// How long the countdown is
+        pub countdown_duration: u64,
+        /// Minimum raised balance to press the button
+        pub min_raise_balance: Balance,
+        /// The balance paid into the game
+        pub pot: Balance,
+    }
+
+    /// A snapshot of the game, as returned by `get_state()`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct GameState {
+        /// The account of the last caller
+        pub last_press_caller: AccountId,
+        /// The timestamp of the last call
+        pub last_press_timestamp: u64,
         /// How long the countdown is
-        countdown_duration: u64,
+        pub countdown_duration: u64,
         /// Minimum raised balance to press the button
-        min_raise_balance: Balance,
+        pub min_raise_balance: Balance,
+        /// The countdown until the next payout
+        pub remaining_countdown: u64,
+        /// The contract balance
+        pub balance: Balance,
+    }
+
+    /// The involvement of an account in the game, as returned by `get_participation()`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct Participation {
+        /// How often the account has pressed the button
+        pub presses: u64,
+        /// The amount the account has raised the pot by, after fees
+        pub contributed: Balance,
+        /// The amount the account has won across all rounds
+        pub won: Balance,
+        /// Whether the account is the current leader
+        pub holds_button: bool,
+    }
+
+    /// How a payout settles the balance of the contract, as returned by `payout_proof()`.
+    /// `reserved + fees + reseed + seed + winner_amount + runner_up_amount + third_amount + consolation + charity + bounty
+    /// + dust + residual` always equals `balance`.
+    /// A won jackpot bonus is moved from `fees` and `reseed` to `winner_amount`. Only the payout rolls the jackpot,
+    /// so the proof returned by `payout_proof()` never contains a bonus.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub struct PayoutProof {
+        /// The balance of the contract, without the pots of the additional games and the credited winnings
+        pub balance: Balance,
+        /// The part of the existential deposit kept in the contract
+        pub reserved: Balance,
+        /// The house fees paid to the beneficiary
+        pub fees: Balance,
+        /// The house fees kept in the contract to seed the next round
+        pub reseed: Balance,
+        /// The creator's seed refunded to the creator
+        pub seed: Balance,
+        /// The pot shared by the payout, i.e. the balance minus the reserve, the fees and the seed
+        pub pot: Balance,
+        /// The winner's share of the pot, in basis points
+        pub winner_share_bps: u16,
+        /// The amount paid to the winner, including the jackpot bonus
+        pub winner_amount: Balance,
+        /// The accrued house fees added to the winner's prize by the random jackpot
+        pub jackpot_bonus: Balance,
+        /// The amount paid to the runner-up by the podium split
+        pub runner_up_amount: Balance,
+        /// The amount paid to the third-to-last distinct presser by the podium split
+        pub third_amount: Balance,
+        /// The amount paid to each consolation recipient
+        pub consolation_each: Balance,
+        /// The total amount paid to the consolation recipients
+        pub consolation: Balance,
+        /// The amount donated to the charity
+        pub charity: Balance,
+        /// The bounty paid to the account that triggers the payout
+        pub bounty: Balance,
+        /// The rounding dust left over by applying the basis points. It stays in the contract.
+        pub dust: Balance,
+        /// The part of the pot that stays in the contract to seed the next round
+        pub residual: Balance,
     }
 
     #[derive(Debug, PartialEq, Eq)]
@@ -42,158 +603,6322 @@ mod the_button {
         CountdownNotPassed,
         /// The caller has not paid enough balance
         InsertCoinToContinue,
+        /// The game config is inconsistent
+        InvalidConfig,
+        /// The caller is not the owner of the contract
+        NotOwner,
+        /// The button has already been pressed
+        GameAlreadyPlayed,
+        /// The round theme exceeds the maximum length
+        ThemeTooLong,
+        /// The caller is not one of the payout approvers
+        NotApprover,
+        /// The caller has already approved the payout
+        AlreadyApproved,
+        /// Not enough approvers have approved the payout yet
+        NotEnoughApprovals,
+        /// Not enough different accounts have pressed the button yet
+        NotEnoughPlayers,
+        /// Too many accounts were passed to a batch query
+        TooManyAccounts,
+        /// The last press has not been held for enough blocks yet
+        HoldTooShort,
+        /// The caller pressed last and has to wait for the self-press cooldown
+        SelfPressCooldown,
+        /// The reopen window has passed and the round can only be paid out
+        RoundOver,
+        /// The caller is not the winner of the round
+        NotWinner,
+        /// The split recipients are empty, too many, or their basis points do not add up to 100%
+        InvalidSplit,
+        /// The whitelist is enabled and the caller is not on it
+        NotWhitelisted,
+        /// The caller is banned from pressing the button
+        Banned,
+        /// The price of a press is higher than the caller accepted
+        PriceChanged,
+        /// The press message is longer than 64 bytes
+        MessageTooLong,
+        /// Another press already happened in this block
+        AlreadyPressedThisBlock,
+        /// The pot is smaller than `min_payout_pot`
+        PotTooSmall,
+        /// The caller is not an allowed relayer
+        NotRelayer,
+        /// The caller is not the current leader
+        NotCurrentLeader,
+        /// The maximum lifetime of the game has passed
+        GameExpired,
+        /// The caller has no credited winnings
+        NothingToWithdraw,
+        /// The minimum interval since the previous press has not passed
+        PressTooSoon,
+        /// The caller is not allowed to trigger the payout
+        NotAuthorizedToPayout,
+        /// There is no game with the given id
+        GameNotFound,
+        /// The maximum number of active games has been reached
+        TooManyGames,
+        /// The start time of the game has not been reached yet
+        GameNotStarted,
+        /// The press or top-up transferred more than `max_raise_balance`
+        OverMaxRaise,
+        /// Presses have to be committed and revealed
+        RevealRequired,
+        /// The caller has not committed to a press
+        NoCommitment,
+        /// The revealed nonce does not match the commitment
+        InvalidReveal,
+        /// The winner of the latest payout has to wait for `winner_reentry_cooldown` to press again
+        WinnerCooldown,
+        /// The quote of the price oracle is older than `max_quote_age`
+        StaleQuote,
+        /// There is no balance to pay out
+        NothingToPayout,
+        /// The round has reached `max_presses_per_round`
+        PressLimitReached,
+        /// The countdown is already frozen
+        CountdownFrozen,
+        /// The countdown is not frozen
+        CountdownNotFrozen,
+        /// The transfer of the balance failed
+        TransferFailed,
+        /// Winnings credited in pull payment mode have not been withdrawn yet
+        UnclaimedWinnings,
+        /// The maximum number of banned accounts has been reached
+        TooManyBannedAccounts,
+        /// Nobody has pressed the button in the current round yet
+        NoLeader,
+        /// The message is only available in perpetual mode
+        NotPerpetual,
     }
     /// Type alias for the contract's `Result` type.
     pub type Result<T> = core::result::Result<T, Error>;
 
+    impl Error {
+        /// Returns the stable numeric code of the error. Codes are never changed or reused,
+        /// so clients can rely on them across versions regardless of the variant order.
+        pub fn to_code(&self) -> u32 {
+            match self {
+                Error::CountdownNotPassed => 1,
+                Error::InsertCoinToContinue => 2,
+                Error::InvalidConfig => 3,
+                Error::NotOwner => 4,
+                Error::GameAlreadyPlayed => 5,
+                Error::ThemeTooLong => 6,
+                Error::NotApprover => 7,
+                Error::AlreadyApproved => 8,
+                Error::NotEnoughApprovals => 9,
+                Error::NotEnoughPlayers => 10,
+                Error::TooManyAccounts => 11,
+                Error::HoldTooShort => 12,
+                Error::SelfPressCooldown => 13,
+                Error::RoundOver => 14,
+                Error::NotWinner => 15,
+                Error::InvalidSplit => 16,
+                Error::NotWhitelisted => 17,
+                Error::Banned => 18,
+                Error::PriceChanged => 19,
+                Error::MessageTooLong => 20,
+                Error::AlreadyPressedThisBlock => 21,
+                Error::PotTooSmall => 22,
+                Error::NotRelayer => 23,
+                Error::NotCurrentLeader => 24,
+                Error::GameExpired => 25,
+                Error::NothingToWithdraw => 26,
+                Error::PressTooSoon => 27,
+                Error::NotAuthorizedToPayout => 28,
+                Error::GameNotFound => 29,
+                Error::TooManyGames => 30,
+                Error::GameNotStarted => 31,
+                Error::OverMaxRaise => 32,
+                Error::RevealRequired => 33,
+                Error::NoCommitment => 34,
+                Error::InvalidReveal => 35,
+                Error::WinnerCooldown => 36,
+                Error::StaleQuote => 37,
+                Error::NothingToPayout => 38,
+                Error::PressLimitReached => 39,
+                Error::CountdownFrozen => 40,
+                Error::CountdownNotFrozen => 41,
+                Error::TransferFailed => 42,
+                Error::UnclaimedWinnings => 43,
+                Error::TooManyBannedAccounts => 44,
+                Error::NoLeader => 45,
+                Error::NotPerpetual => 46,
+            }
+        }
+    }
+
+    /// Emitted when a press resets the countdown.
+    #[ink(event)]
+    pub struct CountdownReset {
+        /// The account that pressed the button
+        #[ink(topic)]
+        caller: AccountId,
+        /// The timestamp at which the new countdown ends
+        new_deadline: u64,
+    }
+
+    /// Emitted for every press, along with `CountdownReset`.
+    #[ink(event)]
+    pub struct ButtonPressed {
+        /// The account that pressed the button
+        #[ink(topic)]
+        caller: AccountId,
+        /// The round of the press
+        #[ink(topic)]
+        round: u32,
+        /// The amount the press added to the pot
+        amount: Balance,
+        /// The message left by the press, if any
+        message: Vec<u8>,
+    }
+
+    /// Emitted when the winner of a round is paid out.
+    #[ink(event)]
+    pub struct RewardClaimed {
+        /// The winner of the round
+        #[ink(topic)]
+        winner: AccountId,
+        /// The round that was paid out
+        #[ink(topic)]
+        round: u32,
+        /// The amount won
+        amount: Balance,
+    }
+
+    /// Emitted when a payout donates part of the pot to the charity.
+    #[ink(event)]
+    pub struct CharityDonated {
+        /// The account of the charity
+        #[ink(topic)]
+        charity: AccountId,
+        /// The amount donated
+        amount: Balance,
+    }
+
+    /// Emitted when the button of an additional game is pressed.
+    #[ink(event)]
+    pub struct GamePressed {
+        /// The id of the game
+        #[ink(topic)]
+        game_id: u32,
+        /// The account that pressed the button
+        #[ink(topic)]
+        caller: AccountId,
+        /// The amount the press added to the pot of the game
+        amount: Balance,
+        /// The timestamp at which the new countdown of the game ends
+        new_deadline: u64,
+    }
+
+    /// Emitted when the pot of an additional game is paid out.
+    #[ink(event)]
+    pub struct GamePaidOut {
+        /// The id of the game
+        #[ink(topic)]
+        game_id: u32,
+        /// The winner of the game
+        #[ink(topic)]
+        winner: AccountId,
+        /// The amount won
+        amount: Balance,
+    }
+
+    /// Emitted when someone adds to the pot without pressing the button.
+    #[ink(event)]
+    pub struct PotFunded {
+        /// The account that funded the pot
+        #[ink(topic)]
+        donor: AccountId,
+        /// The amount added to the pot
+        amount: Balance,
+    }
+
+    /// Emitted when someone tips the current leader.
+    #[ink(event)]
+    pub struct LeaderTipped {
+        /// The account that sent the tip
+        #[ink(topic)]
+        tipper: AccountId,
+        /// The leader that received the tip
+        #[ink(topic)]
+        leader: AccountId,
+        /// The amount of the tip
+        amount: Balance,
+    }
+
+    /// Emitted when the ownership of the contract is transferred.
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        /// The account that owned the contract before
+        #[ink(topic)]
+        previous_owner: AccountId,
+        /// The account that owns the contract now
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// Emitted when the owner resets the statistics for a new season.
+    #[ink(event)]
+    pub struct StatsReset {
+        /// The season that starts
+        season: u32,
+    }
+
+    /// Returns `bps` basis points of `amount`, rounded down.
+    fn bps_of(amount: Balance, bps: u16) -> Balance {
+        const DENOMINATOR: Balance = BPS_DENOMINATOR as Balance;
+        let bps = Balance::from(bps);
+        (amount / DENOMINATOR)
+            .saturating_mul(bps)
+            .saturating_add((amount % DENOMINATOR).saturating_mul(bps) / DENOMINATOR)
+    }
+
+    impl TheButton {
+        /// The constructor initializes the contract countdown duration in milliseconds.
+        /// The contract caller and timestamp are set to the caller and the block timestamp.
+        #[ink(constructor)]
+        pub fn new(countdown_duration: u64, min_raise_balance: Balance) -> Self {
+            Self::init(GameConfig::new(countdown_duration, min_raise_balance))
+        }
+
+        /// Initializes the contract with a full game config.
+        /// The endowment seeds the pot, or is refunded on the first payout if `refund_creator_seed` is set.
+        /// If the config is inconsistent, the error `InvalidConfig` is returned.
+        #[ink(constructor, payable)]
+        pub fn with_config(config: GameConfig) -> Result<Self> {
+            config.validate()?;
+            let mut contract = Self::init(config);
+            if contract.config.refund_creator_seed {
+                contract.creator_seed = Self::env().transferred_value();
+            }
+            Ok(contract)
+        }
+
+        /// Initializes the contract from a snapshot of an existing game, e.g. to migrate it.
+        /// `past_winners` restores the lifetime winnings of each account.
+        /// If there are more than 64 past winners, the error `TooManyAccounts` is returned.
+        /// If an account is listed more than once, the error `InvalidConfig` is returned.
+        #[ink(constructor)]
+        pub fn restore(
+            countdown_duration: u64,
+            min_raise_balance: Balance,
+            press_count: u64,
+            total_volume: Balance,
+            past_winners: Vec<(AccountId, Balance)>,
+        ) -> Result<Self> {
+            if past_winners.len() > MAX_BATCH_ACCOUNTS {
+                return Err(Error::TooManyAccounts);
+            }
+            let mut contract = Self::new(countdown_duration, min_raise_balance);
+            for &(winner, won) in &past_winners {
+                if contract.total_won.insert(winner, &won).is_some() {
+                    return Err(Error::InvalidConfig);
+                }
+            }
+            contract.press_count = press_count;
+            contract.total_volume = total_volume;
+            Ok(contract)
+        }
+
+        /// The default constructor initializes the contract with a countdown duration of 24 hours
+        /// and a minimum raised balance of 1e10 units. (1 PAS, 1 DOT, 0.01 KSM)
+        #[ink(constructor)]
+        pub fn default() -> Self {
+            Self::new(86400 * 1000, 10_000_000_000)
+        }
+        
+        /// The caller has to pay at least `min_next_press()` on top of the house fee and the flat
+        /// `press_fee` to press the button. The press fee is forwarded to the beneficiary right away.
+        /// The last caller and timestamp are updated. This resets the countdown.
+        /// A press within `anti_snipe_window` of the deadline extends the new countdown by `anti_snipe_extension`.
+        /// If the caller has not paid enough balance, the error `InsertCoinToContinue` is returned.
+        /// If the caller pressed last and `self_press_cooldown` has not passed, the error `SelfPressCooldown` is returned.
+        /// If `min_press_interval` has not passed since the previous press, the error `PressTooSoon` is returned.
+        /// If the caller won the latest payout less than `winner_reentry_cooldown` ago, the error `WinnerCooldown` is returned.
+        /// A press after the deadline revives the round, unless `reopen_window` has passed as well,
+        /// in which case the error `RoundOver` is returned.
+        /// If the whitelist is enabled and the caller is not on it, the error `NotWhitelisted` is returned.
+        /// If the caller is banned, the error `Banned` is returned.
+        /// If the maximum lifetime of the game has passed, the error `GameExpired` is returned.
+        /// If the round has reached `max_presses_per_round`, the error `PressLimitReached` is returned.
+        /// If `required_gross_payment()` exceeds `max_acceptable_price`, the error `PriceChanged` is returned.
+        /// With a `price_oracle`, the rate is fetched first. If neither the fetched nor the cached quote is
+        /// younger than `max_quote_age`, the error `StaleQuote` is returned.
+        /// Presses with the same timestamp are ordered by block, so the press in the later block wins.
+        /// Within the same block the last press wins, unless `first_press_per_block_wins` is set,
+        /// in which case the error `AlreadyPressedThisBlock` is returned for any further press.
+        /// If `commit_reveal` is enabled, the error `RevealRequired` is returned and `reveal()` has to be used instead.
+        #[ink(message, payable)]
+        pub fn press(&mut self, max_acceptable_price: Balance) -> Result<()> {
+            self.ensure_no_reveal_required()?;
+            self.press_with(self.env().caller(), max_acceptable_price, Vec::new())
+        }
+
+        /// Commits the caller to a press that is made later with `reveal()`. `hash` is the
+        /// `get_commitment_hash()` of the caller and a secret nonce. A new commitment replaces the previous one.
+        #[ink(message)]
+        pub fn commit(&mut self, hash: Hash) {
+            self.commitments.insert(self.env().caller(), &hash);
+        }
+
+        /// Presses the button like `press()` by revealing the nonce of the caller's commitment.
+        /// The commitment is used up by a successful press.
+        /// If the caller has not committed, the error `NoCommitment` is returned.
+        /// If the nonce does not match the commitment, the error `InvalidReveal` is returned.
+        #[ink(message, payable)]
+        pub fn reveal(&mut self, nonce: u64, max_acceptable_price: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let Some(commitment) = self.commitments.get(caller) else {
+                return Err(Error::NoCommitment);
+            };
+            if commitment != self.get_commitment_hash(caller, nonce) {
+                return Err(Error::InvalidReveal);
+            }
+
+            self.press_with(caller, max_acceptable_price, Vec::new())?;
+            self.commitments.remove(caller);
+            Ok(())
+        }
+
+        /// Returns the hash `account` has to commit to press with `reveal(nonce)`
+        #[ink(message)]
+        pub fn get_commitment_hash(&self, account: AccountId, nonce: u64) -> Hash {
+            Hash::from(self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(account, nonce)))
+        }
+
+        /// Presses the button like `press()` and leaves a short message, which is kept until the next press.
+        /// If the message is longer than 64 bytes, the error `MessageTooLong` is returned.
+        #[ink(message, payable)]
+        pub fn press_with_message(&mut self, max_acceptable_price: Balance, message: Vec<u8>) -> Result<()> {
+            if message.len() > MAX_PRESS_MESSAGE_LEN {
+                return Err(Error::MessageTooLong);
+            }
+            self.ensure_no_reveal_required()?;
+
+            self.press_with(self.env().caller(), max_acceptable_price, message)
+        }
+
+        /// Return how much `account` has won from this contract across all rounds
+        #[ink(message)]
+        pub fn get_total_won(&self, account: AccountId) -> Balance {
+            self.total_won.get(account).unwrap_or(0)
+        }
+
+        /// Return how `account` has taken part in the game: its presses, the amount it raised
+        /// the pot by, its lifetime winnings and whether it currently holds the button
+        #[ink(message)]
+        pub fn get_participation(&self, account: AccountId) -> Participation {
+            Participation {
+                presses: self.get_presses(account),
+                contributed: self.contributed.get(account).unwrap_or(0),
+                won: self.get_total_won(account),
+                holds_button: self.round_press_count > 0 && self.is_winning(account),
+            }
+        }
+
+        /// Return the timestamps of the latest `press_history_size` presses, oldest first
+        #[ink(message)]
+        pub fn get_recent_press_times(&self) -> Vec<u64> {
+            self.recent_press_times.clone()
+        }
+
+        /// Return the presses per hour over the span of the recorded press timestamps.
+        /// Returns zero if fewer than two presses are recorded or they all share one timestamp.
+        #[ink(message)]
+        pub fn get_press_rate_per_hour(&self) -> u64 {
+            let (Some(&oldest), Some(&newest)) = (self.recent_press_times.first(), self.recent_press_times.last()) else {
+                return 0;
+            };
+            let intervals = (self.recent_press_times.len() as u64).saturating_sub(1);
+
+            intervals.saturating_mul(3_600_000).checked_div(newest.saturating_sub(oldest)).unwrap_or(0)
+        }
+
+        /// Presses the button on behalf of `presser`, who is recorded as the last caller and can win the pot.
+        /// The relayer pays for the press. All the rules of `press()` apply to `presser`.
+        /// If the caller is not an allowed relayer, the error `NotRelayer` is returned.
+        #[ink(message, payable)]
+        pub fn press_for(&mut self, max_acceptable_price: Balance, presser: AccountId) -> Result<()> {
+            if !self.relayers.contains(self.env().caller()) {
+                return Err(Error::NotRelayer);
+            }
+            self.ensure_no_reveal_required()?;
+
+            self.press_with(presser, max_acceptable_price, Vec::new())
+        }
+
+        /// Return the message left by the last press, which is empty if it did not leave one
+        #[ink(message)]
+        pub fn get_last_press_message(&self) -> Vec<u8> {
+            self.last_press_message.clone()
+        }
+
+        /// Lets the current leader add to the pot and reset the countdown by paying at least `top_up_min`
+        /// instead of a full press. A top-up does not count as a press.
+        /// If the caller is not the leader of the round, the error `NotCurrentLeader` is returned.
+        /// If nothing or less than `top_up_min` was paid, the error `InsertCoinToContinue` is returned.
+        /// If more than `max_raise_balance` was paid, the error `OverMaxRaise` is returned.
+        /// A banned leader cannot top up, and the `reopen_window` applies like for a press.
+        #[ink(message, payable)]
+        pub fn top_up(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if self.blacklist.contains(caller) {
+                return Err(Error::Banned);
+            }
+            if caller != self.last_press_caller || self.round_press_count == 0 {
+                return Err(Error::NotCurrentLeader);
+            }
+            if self.is_expired() {
+                return Err(Error::GameExpired);
+            }
+            if let Some(reopen_window) = self.config.reopen_window {
+                let (passed, duration) = self.countdown_progress();
+                if passed >= duration.saturating_add(reopen_window) {
+                    return Err(Error::RoundOver);
+                }
+            }
+            let transferred = self.env().transferred_value();
+            if transferred == 0 || transferred < self.config.top_up_min {
+                return Err(Error::InsertCoinToContinue);
+            }
+            if self.config.max_raise_balance != 0 && transferred > self.config.max_raise_balance {
+                return Err(Error::OverMaxRaise);
+            }
+
+            self.total_volume = self.total_volume.saturating_add(transferred);
+            let contributed = self.contributed.get(caller).unwrap_or(0).saturating_add(transferred);
+            self.contributed.insert(caller, &contributed);
+            self.last_press_timestamp = self.env().block_timestamp();
+            self.last_press_block = self.env().block_number();
+            self.countdown_extension = 0;
+            self.restart_frozen_clock();
+            self.payout_approvals.clear();
+
+            self.env().emit_event(CountdownReset {
+                caller,
+                new_deadline: self.countdown_deadline(),
+            });
+            Ok(())
+        }
+
+        /// Forwards the transferred value to the current leader as a tip. It is not added to the pot,
+        /// and the last caller, timestamp and countdown are left untouched.
+        /// If nothing was paid, the error `InsertCoinToContinue` is returned.
+        /// If nobody has pressed in the current round, the error `NoLeader` is returned.
+        /// If the transfer to the leader fails, the error `TransferFailed` is returned.
+        #[ink(message, payable)]
+        pub fn tip_leader(&mut self) -> Result<()> {
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return Err(Error::InsertCoinToContinue);
+            }
+            if self.round_press_count == 0 {
+                return Err(Error::NoLeader);
+            }
+
+            let leader = self.last_press_caller;
+            self.env().transfer(leader, amount).map_err(|_| Error::TransferFailed)?;
+            self.env().emit_event(LeaderTipped {
+                tipper: self.env().caller(),
+                leader,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Adds the transferred value to the pot without pressing the button.
+        /// The last caller, timestamp and countdown are left untouched.
+        #[ink(message, payable)]
+        pub fn donate(&mut self) {
+            self.env().emit_event(PotFunded {
+                donor: self.env().caller(),
+                amount: self.env().transferred_value(),
+            });
+        }
+
+        /// Claims the reward if 24 hours have passed since the last press.
+        /// The accrued house fees are transferred to the beneficiary and `winner_share_bps` of
+        /// the rest of the balance of the contract to the last user who pressed the button.
+        /// `consolation_bps` of the pot is split evenly between the last `consolation_count` other pressers
+        /// and `charity_bps` of the pot is donated to the charity.
+        /// The caller receives `payout_bounty_bps` of the pot as a bounty for triggering the payout.
+        /// The rest is split between the last three distinct pressers according to `podium_bps`, if configured.
+        /// With a chance of `jackpot_chance_bps`, the accrued house fees are added to the winner's prize instead.
+        /// The jackpot roll is predictable, see `GameConfig::random_jackpot`.
+        /// The `on_payout_callback` contract, if any, is notified of the winner and the amount.
+        /// If the countdown has not passed yet, the error `CountdownNotPassed` is returned,
+        /// unless the maximum lifetime of the game has passed or the round has reached `max_presses_per_round`.
+        /// If fewer than `min_unique_players` accounts have pressed the button in the round, the error `NotEnoughPlayers` is returned.
+        /// If the last press was less than `min_hold_blocks` blocks ago, the error `HoldTooShort` is returned.
+        /// If the pot is smaller than `min_payout_pot`, the error `PotTooSmall` is returned.
+        /// The existential deposit stays in the contract so that the transfers cannot reap it.
+        /// The contract is terminated after the reward is paid out. Any remaining balance is sent to the winner.
+        /// In perpetual mode the contract is not terminated and a new round starts instead.
+        /// If the balance is zero, nothing is transferred and the contract is still terminated.
+        /// In perpetual mode the error `NothingToPayout` is returned instead of starting a new round
+        /// if the pot is empty, i.e. nothing but the reserved existential deposit and the house fees is left.
+        /// If `payout_access` does not allow the caller to trigger the payout, the error `NotAuthorizedToPayout` is returned.
+        /// If additional games are still active and the contract is not perpetual, the error `GameAlreadyPlayed` is returned.
+        #[ink(message)]
+        pub fn payout(&mut self) -> Result<()> {
+            self.ensure_payout_access(self.last_press_caller)?;
+            self.ensure_payout_allowed()?;
+
+            // transfer the fees to the beneficiary and the winner's share of the pot to the winner
+            let proof = self.payout_settlement();
+            if proof.balance == 0 {
+                return self.finish_payout();
+            }
+            self.pay_fees_and_seed(&proof);
+            self.pay_consolations(proof.consolation_each);
+            self.donate_to_charity(proof.charity);
+            self.pay_bounty(proof.bounty);
+            self.pay_podium(&proof);
+            if self.config.pull_payments {
+                self.credit(self.last_press_caller, proof.winner_amount);
+            } else {
+                let _result = self.deliver_prize(self.last_press_caller, proof.winner_amount);
+            }
+            self.record_win(self.last_press_caller, proof.winner_amount);
+            self.notify_payout(self.last_press_caller, proof.winner_amount);
+
+            self.finish_payout()
+        }
+
+        /// Pays out the round like `payout()`, but splits the winner's share between `recipients`
+        /// according to their basis points, which have to add up to 100%. Only the winner can call this.
+        /// The rounding dust of the split goes to the last recipient.
+        /// Each share is delivered like the prize of `payout()`, to the recipient's payout destination
+        /// and swapped to the stable token if the recipient opted in.
+        /// If a transfer to a recipient fails, the error `TransferFailed` is returned.
+        /// If there are no recipients, more than 8, or their basis points do not add up to 100%,
+        /// the error `InvalidSplit` is returned.
+        /// If `payout_access` does not allow the winner to trigger the payout, the error `NotAuthorizedToPayout` is returned.
+        #[ink(message)]
+        pub fn claim_split(&mut self, recipients: Vec<(AccountId, u16)>) -> Result<()> {
+            if self.env().caller() != self.last_press_caller {
+                return Err(Error::NotWinner);
+            }
+            self.ensure_payout_access(self.last_press_caller)?;
+            let total_bps: u32 = recipients.iter().map(|&(_, bps)| u32::from(bps)).sum();
+            if recipients.is_empty()
+                || recipients.len() > MAX_SPLIT_RECIPIENTS
+                || total_bps != u32::from(BPS_DENOMINATOR)
+            {
+                return Err(Error::InvalidSplit);
+            }
+            self.ensure_payout_allowed()?;
+
+            let proof = self.payout_settlement();
+            self.pay_fees_and_seed(&proof);
+            self.pay_consolations(proof.consolation_each);
+            self.donate_to_charity(proof.charity);
+            self.pay_bounty(proof.bounty);
+            self.pay_podium(&proof);
+            let mut remaining = proof.winner_amount;
+            for (index, &(recipient, bps)) in recipients.iter().enumerate() {
+                let amount = if index.saturating_add(1) == recipients.len() {
+                    remaining
+                } else {
+                    bps_of(proof.winner_amount, bps)
+                };
+                remaining = remaining.saturating_sub(amount);
+                if self.config.pull_payments {
+                    self.credit(recipient, amount);
+                } else {
+                    self.deliver_prize(recipient, amount)?;
+                }
+            }
+            self.record_win(self.last_press_caller, proof.winner_amount);
+            self.notify_payout(self.last_press_caller, proof.winner_amount);
+
+            self.finish_payout()
+        }
+
+        /// Transfers the winnings credited to the caller in pull payment mode to their payout destination.
+        /// If nothing is credited to the caller, the error `NothingToWithdraw` is returned.
+        /// If the transfer fails, the error `TransferFailed` is returned and the credit is kept.
+        #[ink(message)]
+        pub fn withdraw(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let amount = self.get_withdrawable(caller);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.withdrawable.remove(caller);
+            self.total_withdrawable = self.total_withdrawable.saturating_sub(amount);
+            self.env()
+                .transfer(self.get_payout_destination(caller), amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        /// Returns the winnings credited to `account` that it can collect with `withdraw()`
+        #[ink(message)]
+        pub fn get_withdrawable(&self, account: AccountId) -> Balance {
+            self.withdrawable.get(account).unwrap_or(0)
+        }
+
+        /// Returns the winner and the amount they would receive if `payout()` was called now,
+        /// or the error `payout()` would return. Nothing is changed or transferred.
+        /// The random jackpot is not rolled, so a won jackpot bonus is not included.
+        #[ink(message)]
+        pub fn simulate_payout(&self) -> Result<(AccountId, Balance)> {
+            self.ensure_payout_access(self.last_press_caller)?;
+            self.ensure_payout_allowed()?;
+            Ok((self.last_press_caller, self.payout_proof().winner_amount))
+        }
+
+        /// Returns the breakdown of how `payout()` would settle the current balance,
+        /// so that the settlement can be verified independently. The random jackpot is not rolled.
+        #[ink(message)]
+        pub fn payout_proof(&self) -> PayoutProof {
+            let balance = self
+                .env()
+                .balance()
+                .saturating_sub(self.games_pot)
+                .saturating_sub(self.total_withdrawable);
+            let reserved = self.config.existential_deposit.min(balance);
+            let fees = self.accrued_fees.min(balance.saturating_sub(reserved));
+            let seed = self.creator_seed.min(balance.saturating_sub(reserved).saturating_sub(fees));
+            let pot = balance.saturating_sub(reserved).saturating_sub(fees).saturating_sub(seed);
+            let winner_share_bps = self.config.winner_share_bps;
+            let residual = bps_of(pot, BPS_DENOMINATOR.saturating_sub(winner_share_bps));
+            // the division dust of the consolation pot stays in the contract
+            let recipients = self.consolation_recipients().len() as Balance;
+            let consolation_pot = if recipients > 0 { bps_of(pot, self.config.consolation_bps) } else { 0 };
+            let consolation_each = consolation_pot.checked_div(recipients).unwrap_or(0);
+            let consolation = consolation_each.saturating_mul(recipients);
+            let charity = bps_of(pot, self.config.charity_bps);
+            let bounty = bps_of(pot, self.config.payout_bounty_bps);
+            let prize = bps_of(pot, winner_share_bps)
+                .saturating_sub(consolation_pot)
+                .saturating_sub(charity)
+                .saturating_sub(bounty);
+            let (runner_up_amount, third_amount) = self.podium_amounts(prize);
+            let winner_amount = prize.saturating_sub(runner_up_amount).saturating_sub(third_amount);
+            let reseed = if self.config.reseed_fees { fees } else { 0 };
+
+            PayoutProof {
+                balance,
+                reserved,
+                fees: fees.saturating_sub(reseed),
+                reseed,
+                seed,
+                pot,
+                winner_share_bps,
+                winner_amount,
+                jackpot_bonus: 0,
+                runner_up_amount,
+                third_amount,
+                consolation_each,
+                consolation,
+                charity,
+                bounty,
+                dust: pot
+                    .saturating_sub(prize)
+                    .saturating_sub(consolation)
+                    .saturating_sub(charity)
+                    .saturating_sub(bounty)
+                    .saturating_sub(residual),
+                residual,
+            }
+        }
+
+        /// Approves paying out the current leader. Only approvers can call this, and only once
+        /// the countdown has passed. Approvals are reset by any new press.
+        #[ink(message)]
+        pub fn approve_payout(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if !self.config.approvers.contains(&caller) {
+                return Err(Error::NotApprover);
+            }
+            if self.get_countdown() > 0 && !self.is_expired() && !self.press_limit_reached() {
+                return Err(Error::CountdownNotPassed);
+            }
+            if self.payout_approvals.contains(&caller) {
+                return Err(Error::AlreadyApproved);
+            }
+
+            self.payout_approvals.push(caller);
+            Ok(())
+        }
+
+        /// Return how many approvers have approved paying out the current leader
+        #[ink(message)]
+        pub fn get_approval_count(&self) -> u32 {
+            u32::try_from(self.payout_approvals.len()).unwrap_or(u32::MAX)
+        }
+
+        /// Cancels a game nobody has played. Only the owner can call this, and only once
+        /// the countdown has passed without a single press.
+        /// The balance of the contract is returned to the owner and the contract is terminated.
+        /// If additional games are still active, the error `GameAlreadyPlayed` is returned.
+        #[ink(message)]
+        pub fn cancel_if_no_plays(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.press_count > 0 || !self.active_games.is_empty() {
+                return Err(Error::GameAlreadyPlayed);
+            }
+            if self.get_countdown() > 0 {
+                return Err(Error::CountdownNotPassed);
+            }
+
+            self.env().terminate_contract(self.owner);
+        }
+
+        /// Sends the balance to the owner and terminates the contract if nobody claimed the payout
+        /// within `abandonment_grace` after the deadline. Only the owner can call this.
+        /// The accrued house fees are transferred to the beneficiary first.
+        /// If emergency withdrawals are disabled or the grace period has not passed, the error `CountdownNotPassed` is returned.
+        /// If additional games are still active, the error `GameAlreadyPlayed` is returned.
+        /// If credited winnings have not been withdrawn yet, the error `UnclaimedWinnings` is returned.
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let Some(abandonment_grace) = self.config.abandonment_grace else {
+                return Err(Error::CountdownNotPassed);
+            };
+            let (passed, duration) = self.countdown_progress();
+            if passed < duration.saturating_add(abandonment_grace) {
+                return Err(Error::CountdownNotPassed);
+            }
+            if !self.active_games.is_empty() {
+                return Err(Error::GameAlreadyPlayed);
+            }
+            if self.total_withdrawable > 0 {
+                return Err(Error::UnclaimedWinnings);
+            }
+
+            let proof = self.payout_proof();
+            let fees = proof.fees.saturating_add(proof.reseed);
+            if fees > 0 {
+                let _result = self.env().transfer(self.beneficiary, fees);
+            }
+            self.env().terminate_contract(self.owner);
+        }
+
+        /// Force-start a fresh round with the owner as the last caller.
+        /// Only allowed while nobody has pressed in the current round, e.g. right after a payout
+        /// in perpetual mode. Lifetime statistics like the press count are kept.
+        /// The round number and the round theme stay the same, as only a payout starts the next round.
+        /// If the game is not perpetual, the error `NotPerpetual` is returned.
+        #[ink(message)]
+        pub fn restart(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.config.perpetual {
+                return Err(Error::NotPerpetual);
+            }
+            if self.round_press_count > 0 {
+                if self.get_countdown() > 0 {
+                    return Err(Error::CountdownNotPassed);
+                }
+                // the winner of the round has to be paid out first
+                return Err(Error::GameAlreadyPlayed);
+            }
+
+            self.reset_round();
+            Ok(())
+        }
+
+        /// Return the countdown until the next payout, in blocks if the countdown is measured in blocks
+        #[ink(message)]
+        pub fn get_countdown(&self) -> u64 {
+            let (passed, duration) = self.countdown_progress();
+
+            if passed >= duration {
+                return 0;
+            }
+
+            duration.checked_sub(passed).unwrap()
+        }
+
+        /// Return the remaining countdown in seconds, rounded up so that it only reaches zero
+        /// once the countdown has passed. Only meaningful if the countdown is measured in milliseconds.
+        #[ink(message)]
+        pub fn get_seconds_remaining(&self) -> u64 {
+            self.get_countdown().div_ceil(1000)
+        }
+
+        /// Return how much of the countdown has elapsed in basis points, capped at 100%.
+        /// A zero countdown counts as fully elapsed.
+        #[ink(message)]
+        pub fn get_elapsed_fraction_bps(&self) -> u16 {
+            let (passed, duration) = self.countdown_progress();
+            if duration == 0 {
+                return BPS_DENOMINATOR;
+            }
+
+            let fraction = u128::from(passed)
+                .saturating_mul(u128::from(BPS_DENOMINATOR))
+                .checked_div(u128::from(duration))
+                .unwrap_or(0);
+            u16::try_from(fraction).unwrap_or(BPS_DENOMINATOR).min(BPS_DENOMINATOR)
+        }
+
+        /// Return the remaining countdown as `(days, hours, minutes, seconds)`.
+        /// Only meaningful if the countdown is measured in milliseconds.
+        #[ink(message)]
+        pub fn get_countdown_breakdown(&self) -> (u64, u64, u64, u64) {
+            let seconds = self.get_countdown() / 1000;
+
+            (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60, seconds % 60)
+        }
+
+        /// Returns the timestamp or, if the countdown is measured in blocks, the block number
+        /// at which the current countdown ends, including any anti-sniping extension.
+        #[ink(message)]
+        pub fn get_effective_deadline(&self) -> u64 {
+            self.countdown_deadline()
+        }
+
+        /// Returns the countdown duration in milliseconds after every press so far reduced it by
+        /// `countdown_step`, but never below `countdown_floor`.
+        #[ink(message)]
+        pub fn get_active_countdown_duration(&self) -> u64 {
+            self.countdown_duration_after(self.press_count)
+        }
+
+        /// Returns the countdown a press right now would start, including a duration scheduled by
+        /// `set_countdown_duration()`, the reduction by `countdown_step` and any anti-sniping extension,
+        /// in blocks if the countdown is measured in blocks.
+        #[ink(message)]
+        pub fn get_time_to_beat(&self) -> u64 {
+            let duration = if self.config.use_block_number {
+                u64::from(self.config.countdown_blocks)
+            } else {
+                let base = self.next_countdown_duration.unwrap_or(self.config.countdown_duration);
+                self.reduced_countdown_duration(base, self.press_count.saturating_add(1))
+            };
+            let remaining = self.get_countdown();
+            if remaining > 0 && remaining <= self.config.anti_snipe_window {
+                return duration.saturating_add(self.config.anti_snipe_extension);
+            }
+            duration
+        }
+
+        /// Returns how many milliseconds have passed since the contract was created
+        #[ink(message)]
+        pub fn get_contract_age(&self) -> u64 {
+            self.env().block_timestamp().saturating_sub(self.created_at)
+        }
+
+        /// Returns the timestamp from which the button can be pressed
+        #[ink(message)]
+        pub fn get_start_time(&self) -> u64 {
+            self.config.start_time
+        }
+
+        /// Returns the timestamp after which the game cannot be pressed anymore and can be paid out
+        /// right away, or `None` if the game has no maximum lifetime.
+        #[ink(message)]
+        pub fn get_game_expiry(&self) -> Option<u64> {
+            if self.config.max_lifetime == 0 {
+                return None;
+            }
+            Some(self.created_at.saturating_add(self.config.max_lifetime))
+        }
+
+        /// Returns whether the maximum lifetime of the game has passed.
+        #[ink(message)]
+        pub fn is_expired(&self) -> bool {
+            self.get_game_expiry()
+                .is_some_and(|expiry| self.env().block_timestamp() >= expiry)
+        }
+
+        /// Returns the status of the game, derived from the start time, the countdown,
+        /// the maximum lifetime, whether `payout()` would succeed and whether the countdown is frozen.
+        #[ink(message)]
+        pub fn get_status(&self) -> GameStatus {
+            if self.env().block_timestamp() < self.config.start_time {
+                return GameStatus::NotStarted;
+            }
+            if self.ensure_payout_allowed().is_ok() {
+                return GameStatus::PayoutReady;
+            }
+            if self.frozen_at.is_some() {
+                return GameStatus::Paused;
+            }
+            if self.is_expired() {
+                return GameStatus::Ended;
+            }
+            GameStatus::Active
+        }
+
+        /// Returns the timestamp at which the base countdown of the last press ends,
+        /// saturating at `u64::MAX`. Unlike `get_effective_deadline()` it ignores extensions and block mode.
+        #[ink(message)]
+        pub fn get_deadline(&self) -> u64 {
+            self.last_press_timestamp.saturating_add(self.get_active_countdown_duration())
+        }
+
+        /// Returns the account that would win if the payout happened at the timestamp `at`,
+        /// or `None` if the countdown including any extension would still be running by then.
+        /// Only meaningful if the countdown is measured in milliseconds.
+        #[ink(message)]
+        pub fn would_win_at(&self, at: u64) -> Option<AccountId> {
+            let duration = self.get_active_countdown_duration().saturating_add(self.countdown_extension);
+            if at.saturating_sub(self.last_press_timestamp) < duration {
+                return None;
+            }
+            Some(self.last_press_caller)
+        }
+
+        /// Return the milliseconds passed since the last call
+        #[ink(message)]
+        pub fn get_time_since_last_press(&self) -> u64 {
+            self.env().block_timestamp().saturating_sub(self.last_press_timestamp)
+        }
+
+        /// Return the block number of the last call
+        #[ink(message)]
+        pub fn get_last_press_block(&self) -> BlockNumber {
+            self.last_press_block
+        }
+
+        /// Return the account id of the last caller
+        #[ink(message)]
+        pub fn get_last_press_caller(&self) -> AccountId {
+            self.last_press_caller
+        }
+
+        /// Return the account id of the caller before the last caller. Before the first press
+        /// this is the account that created the contract.
+        #[ink(message)]
+        pub fn get_runner_up(&self) -> AccountId {
+            self.runner_up
+        }
+
+        /// Return whether `account` is the current leader
+        #[ink(message)]
+        pub fn is_winning(&self, account: AccountId) -> bool {
+            self.last_press_caller == account
+        }
+
+        /// Return the timestamp of the last call
+        #[ink(message)]
+        pub fn get_last_press_timestamp(&self) -> u64 {
+            self.last_press_timestamp
+        }
+
+        /// Returns the state of the game in a single call, so that front-ends
+        /// do not have to combine the results of several calls.
+        #[ink(message)]
+        pub fn get_state(&self) -> GameState {
+            GameState {
+                last_press_caller: self.last_press_caller,
+                last_press_timestamp: self.last_press_timestamp,
+                countdown_duration: self.get_active_countdown_duration(),
+                min_raise_balance: self.config.min_raise_balance,
+                remaining_countdown: self.get_countdown(),
+                balance: self.env().balance(),
+            }
+        }
+
+        /// Returns the SCALE-encoded `GameState` of `get_state()`, for clients that cache
+        /// or compare the snapshot as a single blob.
+        #[ink(message)]
+        pub fn get_encoded_state(&self) -> Vec<u8> {
+            ink::scale::Encode::encode(&self.get_state())
+        }
+
+        /// Returns how far the chain time is ahead of `client_time`, in milliseconds.
+        /// A negative value means the client clock is ahead of the chain.
+        #[ink(message)]
+        pub fn time_skew(&self, client_time: u64) -> i64 {
+            let chain_time = i64::try_from(self.env().block_timestamp()).unwrap_or(i64::MAX);
+            chain_time.saturating_sub(i64::try_from(client_time).unwrap_or(i64::MAX))
+        }
+
+        /// Returns the parameters the game was created with, including the resolved beneficiary.
+        /// Unlike `get_state()`, these only change when the owner schedules a new countdown duration
+        /// with `set_countdown_duration()`, which takes effect with the next press.
+        #[ink(message)]
+        pub fn get_config(&self) -> GameConfig {
+            GameConfig {
+                beneficiary: Some(self.beneficiary),
+                ..self.config.clone()
+            }
+        }
+
+        /// Returns the highest jackpot tier reached by the total number of presses, if any.
+        /// The tier bonus is informational and only paid if the pot covers it.
+        #[ink(message)]
+        pub fn get_current_tier(&self) -> Option<(u64, Balance)> {
+            self.config
+                .jackpot_tiers
+                .iter()
+                .rev()
+                .find(|&&(threshold, _)| self.press_count >= threshold)
+                .copied()
+        }
+
+        /// Returns how much the contract balance falls short of the bonus of the current jackpot tier.
+        #[ink(message)]
+        pub fn get_tier_shortfall(&self) -> Balance {
+            self.get_current_tier()
+                .map_or(0, |(_, bonus)| bonus.saturating_sub(self.env().balance()))
+        }
+
+        /// Return the stable numeric code of `error`, see `Error::to_code()`
+        #[ink(message)]
+        pub fn error_code(&self, error: Error) -> u32 {
+            error.to_code()
+        }
+
+        /// Return the version of the contract, so that clients can check which features are available
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            CONTRACT_VERSION
+        }
+
+        /// Return the sum of the amounts all presses ever added to the pot, across all rounds
+        #[ink(message)]
+        pub fn get_total_volume(&self) -> Balance {
+            self.total_volume
+        }
+
+        /// Return the average amount a press has added to the pot, or zero if nobody has pressed yet
+        #[ink(message)]
+        pub fn get_average_press_amount(&self) -> Balance {
+            self.total_volume
+                .checked_div(Balance::from(self.get_press_count()))
+                .unwrap_or(0)
+        }
+
+        /// Return the account that owns the contract. This is the creator unless ownership was transferred.
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Transfers the ownership of the contract to `new_owner`. Only the owner can call this.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            let previous_owner = self.owner;
+            self.owner = new_owner;
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            });
+            Ok(())
+        }
+
+        /// Return how often the button has been pressed since the statistics were last reset
+        #[ink(message)]
+        pub fn get_press_count(&self) -> u64 {
+            self.press_count.saturating_sub(self.stats_press_count_base)
+        }
+
+        /// Clears the press count, the total volume, the leaderboard and the press counts of all accounts
+        /// to start a new season. Only the owner can call this. The running round and its countdown are not affected.
+        #[ink(message)]
+        pub fn reset_stats(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.stats_press_count_base = self.press_count;
+            self.total_volume = 0;
+            self.top_pressers.clear();
+            self.stats_season = self.stats_season.saturating_add(1);
+            self.env().emit_event(StatsReset { season: self.stats_season });
+            Ok(())
+        }
+
+        /// Return how often `account` has pressed the button
+        #[ink(message)]
+        pub fn get_presses(&self, account: AccountId) -> u64 {
+            self.press_counts.get((self.stats_season, account)).unwrap_or(0)
+        }
+
+        /// Return how often each of `accounts` has pressed the button, in the same order.
+        /// If more than 64 accounts are passed, the error `TooManyAccounts` is returned.
+        #[ink(message)]
+        pub fn get_presses_for(&self, accounts: Vec<AccountId>) -> Result<Vec<u64>> {
+            if accounts.len() > MAX_BATCH_ACCOUNTS {
+                return Err(Error::TooManyAccounts);
+            }
+
+            Ok(accounts.into_iter().map(|account| self.get_presses(account)).collect())
+        }
+
+        /// Return the accounts with the most presses and their press counts, sorted descending
+        #[ink(message)]
+        pub fn get_leaderboard(&self) -> Vec<(AccountId, u64)> {
+            self.top_pressers.clone()
+        }
+
+        /// Return the 1-based position of `account` on the leaderboard, or `None` if it is not on it.
+        /// Only the top `leaderboard_size` pressers are ranked.
+        #[ink(message)]
+        pub fn get_rank(&self, account: AccountId) -> Option<u32> {
+            self.top_pressers
+                .iter()
+                .position(|&(presser, _)| presser == account)
+                .map(|index| u32::try_from(index).unwrap_or(u32::MAX).saturating_add(1))
+        }
+
+        /// Return how often `who` lost the lead because another account pressed the button
+        #[ink(message)]
+        pub fn get_times_dethroned(&self, who: AccountId) -> u64 {
+            self.times_dethroned.get(who).unwrap_or(0)
+        }
+
+        /// Return how many different accounts have pressed the button in the current round
+        #[ink(message)]
+        pub fn get_unique_player_count(&self) -> u32 {
+            self.unique_player_count
+        }
+
+        /// Sets the account the caller's winnings are sent to, e.g. a cold wallet.
+        /// Setting the caller's own account removes the override.
+        #[ink(message)]
+        pub fn set_payout_destination(&mut self, destination: AccountId) {
+            let caller = self.env().caller();
+            if destination == caller {
+                self.payout_destination.remove(caller);
+            } else {
+                self.payout_destination.insert(caller, &destination);
+            }
+        }
+
+        /// Return the account the winnings of `account` are sent to
+        #[ink(message)]
+        pub fn get_payout_destination(&self, account: AccountId) -> AccountId {
+            self.payout_destination.get(account).unwrap_or(account)
+        }
+
+        /// Sets whether the caller wants their winnings swapped to the stable token.
+        /// Winnings are delivered natively if no router is configured or the swap fails.
+        #[ink(message)]
+        pub fn set_payout_preference(&mut self, stable: bool) {
+            let caller = self.env().caller();
+            if stable {
+                self.stable_payout.insert(caller, &());
+            } else {
+                self.stable_payout.remove(caller);
+            }
+        }
+
+        /// Return whether `account` wants their winnings swapped to the stable token
+        #[ink(message)]
+        pub fn get_payout_preference(&self, account: AccountId) -> bool {
+            self.stable_payout.contains(account)
+        }
+
+        /// Return the number of the current round. Each payout in perpetual mode starts the next round.
+        #[ink(message)]
+        pub fn get_round(&self) -> u32 {
+            self.round
+        }
+
+        /// Return the theme of the current round
+        #[ink(message)]
+        pub fn get_round_theme(&self) -> String {
+            self.round_theme.clone()
+        }
+
+        /// Schedules the theme of the next round. Only the owner can call this.
+        /// The current round keeps its theme; the new one applies once the next round starts.
+        /// If the theme is longer than 64 bytes, the error `ThemeTooLong` is returned.
+        #[ink(message)]
+        pub fn set_round_theme(&mut self, theme: String) -> Result<()> {
+            self.ensure_owner()?;
+            if theme.len() > MAX_ROUND_THEME_LEN {
+                return Err(Error::ThemeTooLong);
+            }
+
+            self.next_round_theme = Some(theme);
+            Ok(())
+        }
+
+        /// Schedules a new countdown duration in milliseconds. Only the owner can call this.
+        /// The running countdown keeps its deadline; the new duration applies from the next press onward.
+        /// If the duration is shorter than `countdown_floor` while the countdown shrinks, the error `InvalidConfig` is returned.
+        #[ink(message)]
+        pub fn set_countdown_duration(&mut self, new: u64) -> Result<()> {
+            self.ensure_owner()?;
+            if self.config.countdown_step > 0 && self.config.countdown_floor > new {
+                return Err(Error::InvalidConfig);
+            }
+
+            self.next_countdown_duration = Some(new);
+            Ok(())
+        }
+
+        /// Freezes the countdown, e.g. during maintenance. Only the owner can call this.
+        /// The time until `resume_countdown()` does not count towards the countdown, so the deadline
+        /// moves by the frozen duration. Presses are still accepted and restart the frozen countdown.
+        /// Only affects countdowns measured in milliseconds.
+        /// If the countdown is already frozen, the error `CountdownFrozen` is returned.
+        #[ink(message)]
+        pub fn freeze_countdown(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.frozen_at.is_some() {
+                return Err(Error::CountdownFrozen);
+            }
+
+            self.frozen_at = Some(self.env().block_timestamp());
+            Ok(())
+        }
+
+        /// Resumes a countdown frozen by `freeze_countdown()`. Only the owner can call this.
+        /// If the countdown is not frozen, the error `CountdownNotFrozen` is returned.
+        #[ink(message)]
+        pub fn resume_countdown(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.frozen_at.is_none() {
+                return Err(Error::CountdownNotFrozen);
+            }
+
+            self.total_frozen = self.frozen_time();
+            self.frozen_at = None;
+            Ok(())
+        }
+
+        /// Enables or disables the whitelist. Only the owner can call this.
+        /// While the whitelist is enabled, only whitelisted accounts can press the button.
+        #[ink(message)]
+        pub fn set_whitelist_enabled(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist_enabled = enabled;
+            Ok(())
+        }
+
+        /// Adds `account` to the whitelist. Only the owner can call this.
+        #[ink(message)]
+        pub fn add_to_whitelist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist.insert(account, &());
+            Ok(())
+        }
+
+        /// Removes `account` from the whitelist. Only the owner can call this.
+        #[ink(message)]
+        pub fn remove_from_whitelist(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.whitelist.remove(account);
+            Ok(())
+        }
+
+        /// Return whether only whitelisted accounts can press the button
+        #[ink(message)]
+        pub fn is_whitelist_enabled(&self) -> bool {
+            self.whitelist_enabled
+        }
+
+        /// Return whether `account` is on the whitelist
+        #[ink(message)]
+        pub fn is_whitelisted(&self, account: AccountId) -> bool {
+            self.whitelist.contains(account)
+        }
+
+        /// Bans `account` from pressing the button. Only the owner can call this.
+        /// A banned account that already holds the button can still win the round.
+        /// If `MAX_BANNED_ACCOUNTS` accounts are already banned, the error `TooManyBannedAccounts` is returned.
+        #[ink(message)]
+        pub fn ban(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            if self.blacklist.contains(account) {
+                return Ok(());
+            }
+            if self.banned_accounts.len() >= MAX_BANNED_ACCOUNTS {
+                return Err(Error::TooManyBannedAccounts);
+            }
+
+            self.blacklist.insert(account, &());
+            self.banned_accounts.push(account);
+            Ok(())
+        }
+
+        /// Lifts the ban of `account`. Only the owner can call this.
+        #[ink(message)]
+        pub fn unban(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.blacklist.remove(account);
+            self.banned_accounts.retain(|&banned| banned != account);
+            Ok(())
+        }
+
+        /// Return whether `account` is banned from pressing the button
+        #[ink(message)]
+        pub fn is_banned(&self, account: AccountId) -> bool {
+            self.blacklist.contains(account)
+        }
+
+        /// Return the banned accounts in the order they were banned
+        #[ink(message)]
+        pub fn get_banned_accounts(&self) -> Vec<AccountId> {
+            self.banned_accounts.clone()
+        }
+
+        /// Allows `account` to press on behalf of other accounts. Only the owner can call this.
+        #[ink(message)]
+        pub fn add_relayer(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.relayers.insert(account, &());
+            Ok(())
+        }
+
+        /// Revokes the relayer permission of `account`. Only the owner can call this.
+        #[ink(message)]
+        pub fn remove_relayer(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.relayers.remove(account);
+            Ok(())
+        }
+
+        /// Return whether `account` can press on behalf of other accounts
+        #[ink(message)]
+        pub fn is_relayer(&self, account: AccountId) -> bool {
+            self.relayers.contains(account)
+        }
+
+        /// Creates an additional game with its own countdown, minimum raise and pot, and returns its id.
+        /// Only the owner can call this. The house fees, the whitelist, the blacklist, `start_time` and
+        /// `max_lifetime` of the contract apply to the additional games as well.
+        /// As long as additional games are active, a contract that is not perpetual cannot be paid out.
+        /// If 32 additional games are active already, the error `TooManyGames` is returned.
+        #[ink(message)]
+        pub fn create_game(&mut self, countdown_duration: u64, min_raise_balance: Balance) -> Result<u32> {
+            self.ensure_owner()?;
+            if self.active_games.len() >= MAX_ACTIVE_GAMES {
+                return Err(Error::TooManyGames);
+            }
+
+            let game_id = self.next_game_id;
+            self.next_game_id = game_id.checked_add(1).ok_or(Error::InvalidConfig)?;
+            self.games.insert(
+                game_id,
+                &ButtonGame {
+                    last_press_caller: self.env().caller(),
+                    last_press_timestamp: self.env().block_timestamp(),
+                    countdown_duration,
+                    min_raise_balance,
+                    pot: 0,
+                },
+            );
+            self.active_games.push(game_id);
+            Ok(game_id)
+        }
+
+        /// Presses the button of the additional game `game_id`, which resets its countdown.
+        /// The press is checked and charged like a press of the main game, except that the game's own
+        /// minimum raise applies.
+        /// If the caller has not paid the game's minimum raise after the fees, the error `InsertCoinToContinue` is returned.
+        #[ink(message, payable)]
+        pub fn press_game(&mut self, game_id: u32) -> Result<()> {
+            let mut game = self.games.get(game_id).ok_or(Error::GameNotFound)?;
+            let presser = self.env().caller();
+            self.ensure_can_press(presser)?;
+            let amount = self.charge_press(game.min_raise_balance)?;
+
+            game.last_press_caller = presser;
+            game.last_press_timestamp = self.env().block_timestamp();
+            game.pot = game.pot.saturating_add(amount);
+            self.games_pot = self.games_pot.saturating_add(amount);
+            self.games.insert(game_id, &game);
+            self.total_volume = self.total_volume.saturating_add(amount);
+            let contributed = self.contributed.get(presser).unwrap_or(0).saturating_add(amount);
+            self.contributed.insert(presser, &contributed);
+
+            self.env().emit_event(GamePressed {
+                game_id,
+                caller: presser,
+                amount,
+                new_deadline: game.last_press_timestamp.saturating_add(game.countdown_duration),
+            });
+            Ok(())
+        }
+
+        /// Pays the pot of the additional game `game_id` to the payout destination of its last caller
+        /// once its countdown has passed. The game is removed afterwards.
+        /// If the countdown has not passed yet, the error `CountdownNotPassed` is returned.
+        /// If `payout_access` does not allow the caller to trigger the payout, the error `NotAuthorizedToPayout` is returned.
+        /// If the transfer fails, the error `TransferFailed` is returned and the game is kept.
+        #[ink(message)]
+        pub fn payout_game(&mut self, game_id: u32) -> Result<()> {
+            let game = self.games.get(game_id).ok_or(Error::GameNotFound)?;
+            self.ensure_payout_access(game.last_press_caller)?;
+            let passed = self.env().block_timestamp().saturating_sub(game.last_press_timestamp);
+            if passed < game.countdown_duration {
+                return Err(Error::CountdownNotPassed);
+            }
+
+            self.games.remove(game_id);
+            self.active_games.retain(|&active| active != game_id);
+            self.games_pot = self.games_pot.saturating_sub(game.pot);
+            if game.pot > 0 {
+                self.env()
+                    .transfer(self.get_payout_destination(game.last_press_caller), game.pot)
+                    .map_err(|_| Error::TransferFailed)?;
+            }
+
+            self.env().emit_event(GamePaidOut {
+                game_id,
+                winner: game.last_press_caller,
+                amount: game.pot,
+            });
+            Ok(())
+        }
+
+        /// Returns the ids of the additional games that have not been paid out yet, in creation order.
+        #[ink(message)]
+        pub fn get_active_games(&self) -> Vec<u32> {
+            self.active_games.clone()
+        }
+
+        /// Returns the additional game `game_id`, or `None` if it does not exist or has been paid out.
+        #[ink(message)]
+        pub fn get_game(&self, game_id: u32) -> Option<ButtonGame> {
+            self.games.get(game_id)
+        }
+
+        /// Returns the contract balance. This is a convenience function to show the contract balance
+        /// in contract explorers.
+        #[ink(message)]
+        pub fn get_balance(&self) -> Balance {
+            self.env().balance()
+        }
+
+        /// Returns the balance split into `(claimable_pot, accrued_fees)`, where the pot is the balance
+        /// shared by the next payout, i.e. without the house fees, the reserved existential deposit
+        /// and the pots of the additional games.
+        #[ink(message)]
+        pub fn get_balance_breakdown(&self) -> (Balance, Balance) {
+            let proof = self.payout_proof();
+            (proof.pot, proof.fees)
+        }
+
+        /// Returns the current pot together with the token decimals, so that clients can format it consistently
+        #[ink(message)]
+        pub fn get_display_amounts(&self) -> (Balance, u8) {
+            (self.payout_proof().pot, self.config.decimals)
+        }
+
+        /// Returns the accrued house fees that the next payout keeps in the contract to seed the following round
+        #[ink(message)]
+        pub fn get_reserved_reseed(&self) -> Balance {
+            self.payout_proof().reseed
+        }
+
+        /// Moves the last press `by` milliseconds into the past, as if that much time had passed.
+        /// Only the owner can call this. Meant for tests and only compiled with the `test-helpers` feature.
+        #[cfg(any(test, feature = "test-helpers"))]
+        #[ink(message)]
+        pub fn advance_last_press(&mut self, by: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.last_press_timestamp = self.last_press_timestamp.saturating_sub(by);
+            Ok(())
+        }
+
+        /// Returns whether the internal bookkeeping is consistent. Meant for tests and audits and
+        /// only compiled with the `invariant-checks` feature.
+        #[cfg(any(test, feature = "invariant-checks"))]
+        #[ink(message)]
+        pub fn check_invariants(&self) -> bool {
+            let tracked = self
+                .accrued_fees
+                .saturating_add(self.games_pot)
+                .saturating_add(self.creator_seed)
+                .saturating_add(self.total_withdrawable);
+
+            self.round_press_count >= u64::from(self.unique_player_count)
+                && self.press_count >= self.round_press_count
+                && self.env().balance() >= tracked
+                && (self.config.max_price == 0 || self.config.min_raise_balance <= self.config.max_price)
+                && self.top_pressers.len() <= self.config.leaderboard_size as usize
+        }
+
+        /// Returns the smallest amount a press has to transfer so that, after the press fee and
+        /// the house fee are deducted, at least `min_next_press()` is added to the pot.
+        #[ink(message)]
+        pub fn required_gross_payment(&self) -> Balance {
+            let min_next_press = self.effective_min_raise();
+            if min_next_press == 0 {
+                return self.config.press_fee;
+            }
+
+            let denominator = Balance::from(BPS_DENOMINATOR);
+            let net_share = denominator.saturating_sub(Balance::from(self.config.fee_bps));
+            min_next_press
+                .saturating_sub(1)
+                .saturating_mul(denominator)
+                .checked_div(net_share)
+                .unwrap_or(Balance::MAX)
+                .saturating_add(1)
+                .saturating_add(self.config.press_fee)
+        }
+
+        /// Returns the minimum amount the next press has to add to the pot, after fees.
+        /// This is `min_raise_balance` for the first press of a round and
+        /// `max(min_raise_balance, last_press_amount + min_increment)` afterwards, capped at `max_price`.
+        #[ink(message)]
+        pub fn min_next_press(&self) -> Balance {
+            self.effective_min_raise()
+        }
+
+        /// Returns the minimum amount the next press has to add to the pot after fees, with the escalation
+        /// and the `max_price` cap applied. `press()` enforces exactly this amount.
+        #[ink(message)]
+        pub fn get_effective_min_raise(&self) -> Balance {
+            self.effective_min_raise()
+        }
+
+        /// Returns the price after `presses` more presses that each pay exactly the price at their turn,
+        /// clamped at `max_price`. Nothing is changed. At most 256 presses are projected.
+        #[ink(message)]
+        pub fn get_price_after(&self, presses: u32) -> Balance {
+            let mut price = self.effective_min_raise();
+            for _ in 0..presses.min(MAX_PRICE_PROJECTION) {
+                price = self.price_after_press_of(price);
+            }
+            price
+        }
+
+        /// Return the last rate quoted by the price oracle and the timestamp of the quote
+        #[ink(message)]
+        pub fn get_oracle_rate(&self) -> (Balance, u64) {
+            (self.oracle_rate, self.oracle_quoted_at)
+        }
+
+        /// Returns the exact total a press has to transfer right now, including the press fee and
+        /// the house fee. Same as `required_gross_payment()`.
+        #[ink(message)]
+        pub fn get_required_payment(&self) -> Balance {
+            self.required_gross_payment()
+        }
+
+        /// Returns the payment a press requires right now and the gas it is advised to be given,
+        /// so that wallets can prefill both. The gas hint is advisory and set by the owner.
+        #[ink(message)]
+        pub fn get_press_cost_estimate(&self) -> (Balance, u64) {
+            (self.required_gross_payment(), self.gas_hint)
+        }
+
+        /// Sets the gas hint returned by `get_press_cost_estimate()`. Only the owner can call this.
+        #[ink(message)]
+        pub fn set_gas_hint(&mut self, gas_hint: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.gas_hint = gas_hint;
+            Ok(())
+        }
+
+        /// Returns the current price of a press, i.e. `min_next_press()` clamped to `max_price`.
+        #[ink(message)]
+        pub fn get_current_price(&self) -> Balance {
+            self.effective_min_raise()
+        }
+
+        fn init(config: GameConfig) -> Self {
+            let caller = Self::env().caller();
+            let created_at = Self::env().block_timestamp();
+            let last_press_timestamp = created_at.max(config.start_time);
+            let beneficiary = config.beneficiary.unwrap_or(caller);
+
+            Self {
+                last_press_caller: caller,
+                last_press_timestamp,
+                config,
+                beneficiary,
+                accrued_fees: 0,
+                owner: caller,
+                press_count: 0,
+                round_theme: String::new(),
+                next_round_theme: None,
+                last_press_amount: 0,
+                payout_approvals: Vec::new(),
+                last_press_block: Self::env().block_number(),
+                players: Mapping::default(),
+                unique_player_count: 0,
+                stable_payout: Mapping::default(),
+                times_dethroned: Mapping::default(),
+                countdown_extension: 0,
+                frozen_at: None,
+                total_frozen: 0,
+                pot_extensions_fired: 0,
+                press_counts: Mapping::default(),
+                round_press_count: 0,
+                top_pressers: Vec::new(),
+                payout_destination: Mapping::default(),
+                whitelist: Mapping::default(),
+                whitelist_enabled: false,
+                blacklist: Mapping::default(),
+                banned_accounts: Vec::new(),
+                recent_pressers: Vec::new(),
+                games: Mapping::default(),
+                next_game_id: 1,
+                games_pot: 0,
+                active_games: Vec::new(),
+                total_volume: 0,
+                last_press_message: Vec::new(),
+                creator_seed: 0,
+                creator: caller,
+                recent_press_times: Vec::new(),
+                total_won: Mapping::default(),
+                contributed: Mapping::default(),
+                relayers: Mapping::default(),
+                created_at,
+                withdrawable: Mapping::default(),
+                total_withdrawable: 0,
+                next_countdown_duration: None,
+                runner_up: caller,
+                commitments: Mapping::default(),
+                last_winner: None,
+                last_payout_timestamp: 0,
+                gas_hint: 0,
+                stats_season: 0,
+                stats_press_count_base: 0,
+                oracle_rate: 0,
+                oracle_quoted_at: 0,
+                round: 1,
+                #[cfg(test)]
+                jackpot_seed_override: None,
+            }
+        }
+
+        /// Performs a press for `presser` as documented on `press()` and stores `message` as the last press message.
+        fn press_with(&mut self, presser: AccountId, max_acceptable_price: Balance, message: Vec<u8>) -> Result<()> {
+            self.ensure_can_press(presser)?;
+            if self.press_limit_reached() {
+                return Err(Error::PressLimitReached);
+            }
+            if let Some(reopen_window) = self.config.reopen_window {
+                let (passed, duration) = self.countdown_progress();
+                if passed >= duration.saturating_add(reopen_window) {
+                    return Err(Error::RoundOver);
+                }
+            }
+
+            let since_last_press = self.env().block_timestamp().saturating_sub(self.last_press_timestamp);
+            if presser == self.last_press_caller
+                && self.round_press_count > 0
+                && since_last_press < self.config.self_press_cooldown
+            {
+                return Err(Error::SelfPressCooldown);
+            }
+            if self.last_winner == Some(presser)
+                && self.env().block_timestamp().saturating_sub(self.last_payout_timestamp)
+                    < self.config.winner_reentry_cooldown
+            {
+                return Err(Error::WinnerCooldown);
+            }
+            if self.round_press_count > 0 && since_last_press < self.config.min_press_interval {
+                return Err(Error::PressTooSoon);
+            }
+            if self.config.first_press_per_block_wins
+                && self.round_press_count > 0
+                && self.env().block_number() == self.last_press_block
+                && self.env().block_timestamp() == self.last_press_timestamp
+            {
+                return Err(Error::AlreadyPressedThisBlock);
+            }
+
+            self.refresh_oracle_rate()?;
+            if self.required_gross_payment() > max_acceptable_price {
+                return Err(Error::PriceChanged);
+            }
+
+            let amount = self.charge_press(self.effective_min_raise())?;
+            self.last_press_amount = amount;
+            self.total_volume = self.total_volume.saturating_add(amount);
+            let contributed = self.contributed.get(presser).unwrap_or(0).saturating_add(amount);
+            self.contributed.insert(presser, &contributed);
+
+            let remaining = self.get_countdown();
+            let sniped = remaining > 0 && remaining <= self.config.anti_snipe_window;
+            self.countdown_extension = if sniped { self.config.anti_snipe_extension } else { 0 };
+            let pot_extension = self.fire_pot_extensions();
+            self.countdown_extension = self.countdown_extension.saturating_add(pot_extension);
+
+            let previous_caller = self.last_press_caller;
+            self.runner_up = previous_caller;
+            self.last_press_caller = presser;
+            if previous_caller != self.last_press_caller && self.players.contains((self.round, previous_caller)) {
+                let times = self.times_dethroned.get(previous_caller).unwrap_or(0);
+                self.times_dethroned.insert(previous_caller, &times.saturating_add(1));
+            }
+            if let Some(duration) = self.next_countdown_duration.take() {
+                self.config.countdown_duration = duration;
+            }
+            self.last_press_timestamp = self.env().block_timestamp();
+            self.last_press_block = self.env().block_number();
+            self.restart_frozen_clock();
+            self.press_count = self.press_count.saturating_add(1);
+            self.round_press_count = self.round_press_count.saturating_add(1);
+            if self.players.insert((self.round, self.last_press_caller), &()).is_none() {
+                self.unique_player_count = self.unique_player_count.saturating_add(1);
+            }
+            let presses = self.get_presses(self.last_press_caller).saturating_add(1);
+            self.press_counts.insert((self.stats_season, self.last_press_caller), &presses);
+            self.update_leaderboard(self.last_press_caller, presses);
+            self.payout_approvals.clear();
+            if self.config.consolation_count > 0 || self.config.podium_bps.is_some() {
+                let caller = self.last_press_caller;
+                self.recent_pressers.retain(|&presser| presser != caller);
+                self.recent_pressers.insert(0, caller);
+                let podium_size = if self.config.podium_bps.is_some() { 3 } else { 0 };
+                self.recent_pressers
+                    .truncate((self.config.consolation_count as usize).saturating_add(1).max(podium_size));
+            }
+
+            self.last_press_message = message.clone();
+            if self.config.press_history_size > 0 {
+                if self.recent_press_times.len() >= self.config.press_history_size as usize {
+                    self.recent_press_times.remove(0);
+                }
+                self.recent_press_times.push(self.last_press_timestamp);
+            }
+
+            self.env().emit_event(CountdownReset {
+                caller: self.last_press_caller,
+                new_deadline: self.countdown_deadline(),
+            });
+            self.env().emit_event(ButtonPressed {
+                caller: self.last_press_caller,
+                round: self.round,
+                amount,
+                message,
+            });
+
+            Ok(())
+        }
+
+        /// Checks the conditions every press has to meet, in the main game as well as in the additional games.
+        fn ensure_can_press(&self, presser: AccountId) -> Result<()> {
+            if self.is_expired() {
+                return Err(Error::GameExpired);
+            }
+            if self.env().block_timestamp() < self.config.start_time {
+                return Err(Error::GameNotStarted);
+            }
+            if self.blacklist.contains(presser) {
+                return Err(Error::Banned);
+            }
+            if self.whitelist_enabled && !self.whitelist.contains(presser) {
+                return Err(Error::NotWhitelisted);
+            }
+            Ok(())
+        }
+
+        /// Takes the press fee and the house fee from the transferred value and returns the amount
+        /// that goes into the pot. If the amount is less than `min_raise`, the error `InsertCoinToContinue` is returned.
+        fn charge_press(&mut self, min_raise: Balance) -> Result<Balance> {
+            // ensure that the caller has paid enough balance after the press fee and the house fee
+            let transferred = self.env().transferred_value();
+            if self.config.max_raise_balance != 0 && transferred > self.config.max_raise_balance {
+                return Err(Error::OverMaxRaise);
+            }
+            let Some(transferred) = transferred.checked_sub(self.config.press_fee) else {
+                return Err(Error::InsertCoinToContinue);
+            };
+            let fee = bps_of(transferred, self.config.fee_bps);
+            let amount = transferred.saturating_sub(fee);
+            if amount < min_raise {
+                return Err(Error::InsertCoinToContinue);
+            }
+            if self.config.press_fee > 0 {
+                let _result = self.env().transfer(self.beneficiary, self.config.press_fee);
+            }
+            self.accrued_fees = self.accrued_fees.saturating_add(fee);
+            Ok(amount)
+        }
+
+        /// Marks the `pot_extensions` newly reached by the pot as fired and returns their total extra duration.
+        fn fire_pot_extensions(&mut self) -> u64 {
+            let pot = self.payout_proof().pot;
+            let mut extra: u64 = 0;
+            for &(threshold, duration) in self.config.pot_extensions.iter().skip(self.pot_extensions_fired as usize) {
+                if pot < threshold {
+                    break;
+                }
+                extra = extra.saturating_add(duration);
+                self.pot_extensions_fired = self.pot_extensions_fired.saturating_add(1);
+            }
+            extra
+        }
+
+        /// Returns whether the round has reached `max_presses_per_round`.
+        fn press_limit_reached(&self) -> bool {
+            self.config.max_presses_per_round > 0 && self.round_press_count >= self.config.max_presses_per_round
+        }
+
+        /// Returns the error `RevealRequired` if presses have to be committed and revealed.
+        fn ensure_no_reveal_required(&self) -> Result<()> {
+            if self.config.commit_reveal {
+                return Err(Error::RevealRequired);
+            }
+            Ok(())
+        }
+
+        /// Returns the error `NotOwner` if the caller is not the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+
+        /// Records the new press count of `account` on the leaderboard. A new account only
+        /// replaces the lowest entry of a full leaderboard if it has more presses.
+        fn update_leaderboard(&mut self, account: AccountId, presses: u64) {
+            let capacity = self.config.leaderboard_size as usize;
+            if capacity == 0 {
+                return;
+            }
+
+            if let Some(index) = self.top_pressers.iter().position(|(entry, _)| *entry == account) {
+                self.top_pressers.remove(index);
+            } else if self.top_pressers.len() >= capacity {
+                match self.top_pressers.last() {
+                    Some(&(_, lowest)) if presses > lowest => {
+                        self.top_pressers.pop();
+                    }
+                    _ => return,
+                }
+            }
+
+            let index = self
+                .top_pressers
+                .iter()
+                .position(|&(_, count)| count < presses)
+                .unwrap_or(self.top_pressers.len());
+            self.top_pressers.insert(index, (account, presses));
+        }
+
+        /// Checks whether `payout_access` allows the caller to trigger the payout to `winner`.
+        fn ensure_payout_access(&self, winner: AccountId) -> Result<()> {
+            let authorized = match self.config.payout_access {
+                PayoutAccess::Anyone => true,
+                PayoutAccess::WinnerOnly => self.env().caller() == winner,
+                PayoutAccess::OwnerOnly => self.env().caller() == self.owner,
+            };
+            if !authorized {
+                return Err(Error::NotAuthorizedToPayout);
+            }
+            Ok(())
+        }
+
+        /// Checks whether the current round can be paid out.
+        fn ensure_payout_allowed(&self) -> Result<()> {
+            // terminating the contract would take the pots of the additional games with it
+            if !self.config.perpetual && !self.active_games.is_empty() {
+                return Err(Error::GameAlreadyPlayed);
+            }
+            if self.unique_player_count < self.config.min_unique_players {
+                return Err(Error::NotEnoughPlayers);
+            }
+            if self.get_countdown() > 0 && !self.is_expired() && !self.press_limit_reached() {
+                return Err(Error::CountdownNotPassed);
+            }
+            if self.env().block_number().saturating_sub(self.last_press_block) < self.config.min_hold_blocks {
+                return Err(Error::HoldTooShort);
+            }
+            if self.payout_proof().pot < self.config.min_payout_pot {
+                return Err(Error::PotTooSmall);
+            }
+            if self.config.perpetual && self.payout_proof().pot == 0 {
+                return Err(Error::NothingToPayout);
+            }
+            if self.get_approval_count() < self.config.approval_threshold {
+                return Err(Error::NotEnoughApprovals);
+            }
+            Ok(())
+        }
+
+        /// Ends the round after the winnings have been paid out. Perpetual games start a new round,
+        /// otherwise the contract is terminated.
+        fn finish_payout(&mut self) -> Result<()> {
+            if self.config.perpetual {
+                self.round = self.round.saturating_add(1);
+                self.start_round();
+                return Ok(());
+            }
+
+            self.env().terminate_contract(self.last_press_caller);
+        }
+
+        /// Transfers the house fees to the beneficiary and refunds the creator's seed to the creator.
+        fn pay_fees_and_seed(&mut self, proof: &PayoutProof) {
+            if proof.fees > 0 {
+                let _result = self.env().transfer(self.beneficiary, proof.fees);
+            }
+            if proof.seed > 0 {
+                self.creator_seed = 0;
+                let _result = self.env().transfer(self.creator, proof.seed);
+            }
+        }
+
+        /// Transfers `amount` to the charity, if any, and emits `CharityDonated`.
+        fn donate_to_charity(&self, amount: Balance) {
+            let Some(charity) = self.config.charity else {
+                return;
+            };
+            if amount == 0 {
+                return;
+            }
+
+            let _result = self.env().transfer(charity, amount);
+            self.env().emit_event(CharityDonated { charity, amount });
+        }
+
+        /// Transfers the payout bounty to the caller.
+        fn pay_bounty(&self, amount: Balance) {
+            if amount > 0 {
+                let _result = self.env().transfer(self.env().caller(), amount);
+            }
+        }
+
+        /// Returns the recent pressers of the round other than the current leader.
+        fn consolation_recipients(&self) -> Vec<AccountId> {
+            self.recent_pressers
+                .iter()
+                .filter(|&&presser| presser != self.last_press_caller)
+                .take(self.config.consolation_count as usize)
+                .copied()
+                .collect()
+        }
+
+        /// Returns the shares of `prize` for the runner-up and the third-to-last distinct presser.
+        /// Shares without a presser stay with the winner.
+        fn podium_amounts(&self, prize: Balance) -> (Balance, Balance) {
+            let Some((_, runner_up_bps, third_bps)) = self.config.podium_bps else {
+                return (0, 0);
+            };
+            let placed = self.podium_recipients().len();
+            let runner_up = if placed >= 1 { bps_of(prize, runner_up_bps) } else { 0 };
+            let third = if placed >= 2 { bps_of(prize, third_bps) } else { 0 };
+            (runner_up, third)
+        }
+
+        /// Returns the runner-up and the third-to-last distinct presser of the round, as far as they exist.
+        fn podium_recipients(&self) -> Vec<AccountId> {
+            if self.config.podium_bps.is_none() {
+                return Vec::new();
+            }
+
+            self.recent_pressers
+                .iter()
+                .filter(|&&presser| presser != self.last_press_caller)
+                .take(2)
+                .copied()
+                .collect()
+        }
+
+        /// Pays the runner-up and the third-to-last distinct presser their podium shares.
+        fn pay_podium(&mut self, proof: &PayoutProof) {
+            for (recipient, amount) in self
+                .podium_recipients()
+                .into_iter()
+                .zip([proof.runner_up_amount, proof.third_amount])
+            {
+                if amount == 0 {
+                    continue;
+                }
+                if self.config.pull_payments {
+                    self.credit(recipient, amount);
+                } else {
+                    let _result = self.env().transfer(recipient, amount);
+                }
+            }
+        }
+
+        /// Pays `amount` to each consolation recipient.
+        fn pay_consolations(&mut self, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+            for recipient in self.consolation_recipients() {
+                if self.config.pull_payments {
+                    self.credit(recipient, amount);
+                } else {
+                    let _result = self.env().transfer(recipient, amount);
+                }
+            }
+        }
+
+        /// Sends the prize to the winner's payout destination, swapped to the stable token through
+        /// the router if the winner opted in. Falls back to a native transfer if the swap fails.
+        /// Returns the error `TransferFailed` if the native transfer fails.
+        fn deliver_prize(&self, winner: AccountId, amount: Balance) -> Result<()> {
+            let destination = self.get_payout_destination(winner);
+            if let Some(router) = self.config.stable_router {
+                if self.stable_payout.contains(winner) {
+                    let swapped = build_call::<Environment>()
+                        .call(router)
+                        .transferred_value(amount)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!("swap_to_stable")))
+                                .push_arg(destination),
+                        )
+                        .returns::<()>()
+                        .try_invoke();
+                    if matches!(swapped, Ok(Ok(()))) {
+                        return Ok(());
+                    }
+                }
+            }
+
+            self.env().transfer(destination, amount).map_err(|_| Error::TransferFailed)
+        }
+
+        /// Credits `amount` to `account` to be collected with `withdraw()`.
+        fn credit(&mut self, account: AccountId, amount: Balance) {
+            let credited = self.get_withdrawable(account).saturating_add(amount);
+            self.withdrawable.insert(account, &credited);
+            self.total_withdrawable = self.total_withdrawable.saturating_add(amount);
+        }
+
+        /// Adds `amount` to the lifetime winnings of `winner` and emits `RewardClaimed`.
+        fn record_win(&mut self, winner: AccountId, amount: Balance) {
+            let won = self.get_total_won(winner).saturating_add(amount);
+            self.total_won.insert(winner, &won);
+            self.last_winner = Some(winner);
+            self.last_payout_timestamp = self.env().block_timestamp();
+            self.env().emit_event(RewardClaimed {
+                winner,
+                round: self.round,
+                amount,
+            });
+        }
+
+        /// Notifies the `on_payout_callback` contract of a payout. The callback gets a bounded amount of gas,
+        /// and a failing callback is ignored so that it cannot block the payout.
+        fn notify_payout(&self, winner: AccountId, amount: Balance) {
+            let Some(callback) = self.config.on_payout_callback else {
+                return;
+            };
+
+            let _result = build_call::<Environment>()
+                .call(callback)
+                .ref_time_limit(PAYOUT_CALLBACK_REF_TIME_LIMIT)
+                .proof_size_limit(PAYOUT_CALLBACK_PROOF_SIZE_LIMIT)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("on_button_payout")))
+                        .push_arg(winner)
+                        .push_arg(amount),
+                )
+                .returns::<()>()
+                .try_invoke();
+        }
+
+        /// Returns `payout_proof()` with the jackpot rolled. Only the payouts call this, so that the
+        /// queries do not hand out whether the current block wins the jackpot.
+        fn payout_settlement(&self) -> PayoutProof {
+            let mut proof = self.payout_proof();
+            if self.jackpot_won() {
+                proof.jackpot_bonus = proof.fees.saturating_add(proof.reseed);
+                proof.winner_amount = proof.winner_amount.saturating_add(proof.jackpot_bonus);
+                proof.fees = 0;
+                proof.reseed = 0;
+            }
+            proof
+        }
+
+        /// Rolls whether the payout of the current round wins the jackpot bonus. The roll is derived
+        /// from public state, so anyone who dry-runs `payout()` knows the outcome in advance.
+        fn jackpot_won(&self) -> bool {
+            if !self.config.random_jackpot || self.config.jackpot_chance_bps == 0 {
+                return false;
+            }
+
+            let seed = self.env().hash_encoded::<ink::env::hash::Blake2x256, _>(&(
+                self.env().block_number(),
+                self.env().block_timestamp(),
+                self.last_press_caller,
+                self.press_count,
+            ));
+            #[cfg(test)]
+            let seed = self.jackpot_seed_override.unwrap_or(seed);
+            let roll = u16::from_le_bytes([seed[0], seed[1]]) % BPS_DENOMINATOR;
+            roll < self.config.jackpot_chance_bps
+        }
+
+        /// Injects the seed of the jackpot roll so that tests can choose its outcome.
+        #[cfg(test)]
+        fn set_jackpot_seed(&mut self, seed: [u8; 32]) {
+            self.jackpot_seed_override = Some(seed);
+        }
+
+        /// Returns the minimum amount the next press has to add to the pot, as documented on `min_next_press()`.
+        /// Both the queries and `press()` rely on it.
+        fn effective_min_raise(&self) -> Balance {
+            self.price_after_press_of(self.last_press_amount)
+        }
+
+        /// Returns the minimum amount of the next press after a press added `last_press_amount` to the pot,
+        /// as documented on `min_next_press()`.
+        fn price_after_press_of(&self, last_press_amount: Balance) -> Balance {
+            let min_raise_balance = self.base_min_raise();
+            if self.config.min_increment == 0 || last_press_amount == 0 {
+                return min_raise_balance;
+            }
+
+            let price = min_raise_balance.max(last_press_amount.saturating_add(self.config.min_increment));
+            if self.config.max_price == 0 {
+                return price;
+            }
+            price.min(self.config.max_price)
+        }
+
+        /// Returns `min_raise_balance`, raised to `reference_min_raise` at the cached oracle rate if there is a price oracle.
+        /// The converted price is capped at `max_price`.
+        fn base_min_raise(&self) -> Balance {
+            if self.config.price_oracle.is_none() {
+                return self.config.min_raise_balance;
+            }
+
+            let price = self
+                .config
+                .min_raise_balance
+                .max(self.config.reference_min_raise.saturating_mul(self.oracle_rate));
+            if self.config.max_price == 0 {
+                return price;
+            }
+            price.min(self.config.max_price)
+        }
+
+        /// Fetches and caches the rate of the price oracle, if any. A failing oracle falls back to the cached rate.
+        /// Returns the error `StaleQuote` if the rate is older than `max_quote_age`.
+        fn refresh_oracle_rate(&mut self) -> Result<()> {
+            let Some(oracle) = self.config.price_oracle else {
+                return Ok(());
+            };
+
+            let quote = build_call::<Environment>()
+                .call(oracle)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("get_rate"))))
+                .returns::<(Balance, u64)>()
+                .try_invoke();
+            if let Ok(Ok((rate, quoted_at))) = quote {
+                if quoted_at >= self.oracle_quoted_at {
+                    self.oracle_rate = rate;
+                    self.oracle_quoted_at = quoted_at;
+                }
+            }
+            if self.env().block_timestamp().saturating_sub(self.oracle_quoted_at) > self.config.max_quote_age {
+                return Err(Error::StaleQuote);
+            }
+            Ok(())
+        }
+
+        /// Returns the countdown duration in milliseconds once `presses` presses reduced it by `countdown_step`.
+        fn countdown_duration_after(&self, presses: u64) -> u64 {
+            self.reduced_countdown_duration(self.config.countdown_duration, presses)
+        }
+
+        /// Returns `duration` reduced by `countdown_step` for every press, but never below `countdown_floor`.
+        fn reduced_countdown_duration(&self, duration: u64, presses: u64) -> u64 {
+            if self.config.countdown_step == 0 {
+                return duration;
+            }
+
+            let reduction = self.config.countdown_step.saturating_mul(presses);
+            duration.saturating_sub(reduction).max(self.config.countdown_floor)
+        }
+
+        /// Returns how far the countdown has progressed since the last press and how long it is,
+        /// either in milliseconds or in blocks.
+        fn countdown_progress(&self) -> (u64, u64) {
+            if self.config.use_block_number {
+                let blocks_passed = self.env().block_number().checked_sub(self.last_press_block).unwrap();
+                let duration = u64::from(self.config.countdown_blocks).saturating_add(self.countdown_extension);
+                return (u64::from(blocks_passed), duration);
+            }
+
+            // the first countdown of a game with a start time begins in the future
+            let time_passed = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(self.last_press_timestamp)
+                .saturating_sub(self.frozen_time());
+            let duration = self.get_active_countdown_duration().saturating_add(self.countdown_extension);
+            (time_passed, duration)
+        }
+
+        /// Returns the timestamp or, if the countdown is measured in blocks, the block number
+        /// at which the current countdown ends.
+        fn countdown_deadline(&self) -> u64 {
+            let start = if self.config.use_block_number {
+                u64::from(self.last_press_block)
+            } else {
+                self.last_press_timestamp.saturating_add(self.frozen_time())
+            };
+
+            start.saturating_add(self.countdown_progress().1)
+        }
+
+        /// Returns the milliseconds the current countdown has been frozen, including a running freeze.
+        fn frozen_time(&self) -> u64 {
+            let running = self
+                .frozen_at
+                .map_or(0, |frozen_at| self.env().block_timestamp().saturating_sub(frozen_at));
+            self.total_frozen.saturating_add(running)
+        }
+
+        /// Forgets the frozen time of the previous countdown when a new one starts.
+        /// A running freeze carries over to the new countdown.
+        fn restart_frozen_clock(&mut self) {
+            self.total_frozen = 0;
+            if self.frozen_at.is_some() {
+                self.frozen_at = Some(self.env().block_timestamp());
+            }
+        }
+
+        /// Starts a new round of a perpetual game and applies the settings scheduled for it.
+        fn start_round(&mut self) {
+            self.reset_round();
+            if let Some(theme) = self.next_round_theme.take() {
+                self.round_theme = theme;
+            }
+        }
+
+        /// Resets the state of the current round, with the owner as the last caller.
+        fn reset_round(&mut self) {
+            self.last_press_caller = self.owner;
+            self.last_press_timestamp = self.env().block_timestamp();
+            self.last_press_block = self.env().block_number();
+            self.accrued_fees = 0;
+            self.last_press_amount = 0;
+            self.payout_approvals.clear();
+            self.countdown_extension = 0;
+            self.restart_frozen_clock();
+            self.pot_extensions_fired = 0;
+            self.round_press_count = 0;
+            self.unique_player_count = 0;
+            self.recent_pressers.clear();
+        }
+    }
+
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn default_works() {
+            // set up simulated environment
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let caller = accounts.alice;
+            let block_timestamp = 12345;
+
+            // Set the caller and block timestamp
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp);
+
+            // Initialize the contract
+            let button = TheButton::new(86400 * 1000, 1000);
+
+            // Check that the contract was initialized correctly
+            assert_eq!(button.get_last_press_timestamp(), block_timestamp);
+            assert_eq!(button.get_last_press_caller(), caller);
+        }
+
+        #[ink::test]
+        fn press_works() {
+            // GIVEN
+
+            // set up simulated environment
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let block_timestamp = 0;
+            // Set the caller and block timestamp
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp);
+
+            // Initialize the contract
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            // Set a new caller and block timestamp
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp + 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // Press the button
+            let result = button.press(Balance::MAX);
+
+            // THEN
+            // Check that the button was pressed successfully
+            assert_eq!(result, Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+            assert_eq!(button.get_last_press_timestamp(), block_timestamp + 1000);
+        }
+
+        #[ink::test]
+        fn press_emits_countdown_reset() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            let event = <CountdownReset as ink::scale::Decode>::decode(&mut &emitted_events[0].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.caller, accounts.bob);
+            assert_eq!(event.new_deadline, 5000 + 86400 * 1000);
+        }
+
+        #[ink::test]
+        fn required_gross_payment_covers_fee() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            for fee_bps in [0, 250, 1_000, 3_333, 9_999] {
+                let config = GameConfig {
+                    fee_bps,
+                    ..GameConfig::new(86400 * 1000, 1000)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+                let gross = button.required_gross_payment();
+
+                // one unit less does not cover the minimum after fees
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(gross - 1);
+                assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+
+                // the gross amount nets at least the minimum
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(gross);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert!(gross - bps_of(gross, fee_bps) >= 1000);
+            }
+        }
+
+        #[ink::test]
+        fn donate_grows_pot_only() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(500);
+            button.donate();
+
+            // THEN
+            assert_eq!(button.get_balance(), 500);
+            assert_eq!(button.get_last_press_caller(), accounts.alice);
+            assert_eq!(button.get_last_press_timestamp(), 0);
+            assert_eq!(button.get_countdown(), 86400 * 1000 - 1000);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 1);
+            let event = <PotFunded as ink::scale::Decode>::decode(&mut &emitted_events[0].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.donor, accounts.bob);
+            assert_eq!(event.amount, 500);
+        }
+
+        #[ink::test]
+        fn cancel_if_no_plays_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN / THEN
+            // the countdown has not passed yet
+            assert_eq!(button.cancel_if_no_plays(), Err(Error::CountdownNotPassed));
+
+            // only the owner can cancel
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.cancel_if_no_plays(), Err(Error::NotOwner));
+
+            // the balance goes back to the owner
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let should_terminate = move || button.cancel_if_no_plays().unwrap();
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                1_000_000,
+            );
+        }
+
+        #[ink::test]
+        fn cancel_if_no_plays_rejects_played_game() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let result = button.cancel_if_no_plays();
+
+            // THEN
+            assert_eq!(result, Err(Error::GameAlreadyPlayed));
+            assert_eq!(button.get_press_count(), 1);
+        }
+
+        #[ink::test]
+        fn cancel_if_no_plays_rejects_active_games() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            let game_id = button.create_game(3600 * 1000, 100).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press_game(game_id), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let result = button.cancel_if_no_plays();
+
+            // THEN
+            assert_eq!(result, Err(Error::GameAlreadyPlayed));
+            assert_eq!(button.get_game(game_id).unwrap().pot, 100);
+        }
+
+        #[ink::test]
+        fn round_theme_changes_at_round_boundary() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            // the theme is scheduled mid-round
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.set_round_theme(String::from("Halloween")), Ok(()));
+
+            // THEN
+            // the current round keeps its theme
+            assert_eq!(button.get_round_theme(), "");
+
+            // the next round starts with the new theme
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+            assert_eq!(button.get_round_theme(), "Halloween");
+            assert_eq!(button.get_last_press_caller(), accounts.alice);
+            assert_eq!(button.get_countdown(), 86400 * 1000);
+        }
+
+        #[ink::test]
+        fn set_round_theme_rejects_invalid_calls() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            let too_long = String::from_utf8(vec![b'a'; 65]).unwrap();
+            assert_eq!(button.set_round_theme(too_long), Err(Error::ThemeTooLong));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.set_round_theme(String::from("Spring")), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn min_next_press_includes_increment() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                min_increment: 100,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // THEN
+            // the first press only needs the minimum
+            assert_eq!(button.min_next_press(), 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1500);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            // the next press has to beat the previous one by the increment
+            assert_eq!(button.min_next_press(), 1600);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1599);
+            assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1600);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.min_next_press(), 1700);
+        }
+
+        #[ink::test]
+        fn winner_share_leaves_remainder_as_next_pot() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                winner_share_bps: 8_000,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_balance_after - bob_balance, 800_000);
+            assert_eq!(button.get_balance(), 200_000);
+        }
+
+        #[ink::test]
+        fn with_config_rejects_invalid_winner_share() {
+            let config = GameConfig {
+                perpetual: true,
+                winner_share_bps: 10_001,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+
+            // a remainder needs a next round to go to
+            let config = GameConfig {
+                winner_share_bps: 5_000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn get_state_matches_getters() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+
+            // WHEN
+            let state = button.get_state();
+
+            // THEN
+            assert_eq!(state.last_press_caller, button.get_last_press_caller());
+            assert_eq!(state.last_press_timestamp, button.get_last_press_timestamp());
+            assert_eq!(state.countdown_duration, 86400 * 1000);
+            assert_eq!(state.min_raise_balance, 1000);
+            assert_eq!(state.remaining_countdown, button.get_countdown());
+            assert_eq!(state.balance, button.get_balance());
+        }
+
+        #[ink::test]
+        fn get_encoded_state_decodes_to_getters() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+
+            // WHEN
+            let encoded = button.get_encoded_state();
+
+            // THEN
+            let state = <GameState as ink::scale::Decode>::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(state.last_press_caller, button.get_last_press_caller());
+            assert_eq!(state.last_press_timestamp, button.get_last_press_timestamp());
+            assert_eq!(state.countdown_duration, button.get_active_countdown_duration());
+            assert_eq!(state.min_raise_balance, 1000);
+            assert_eq!(state.remaining_countdown, button.get_countdown());
+            assert_eq!(state.balance, button.get_balance());
+        }
+
+        #[ink::test]
+        fn payout_requires_approval_threshold() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                approvers: vec![accounts.charlie, accounts.django, accounts.eve],
+                approval_threshold: 2,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // WHEN / THEN
+            assert_eq!(button.approve_payout(), Err(Error::NotApprover));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.approve_payout(), Ok(()));
+            assert_eq!(button.approve_payout(), Err(Error::AlreadyApproved));
+            assert_eq!(button.payout(), Err(Error::NotEnoughApprovals));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(button.approve_payout(), Ok(()));
+            assert_eq!(button.payout(), Ok(()));
+            assert_eq!(button.get_approval_count(), 0);
+        }
+
+        #[ink::test]
+        fn press_resets_payout_approvals() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                approvers: vec![accounts.charlie, accounts.django],
+                approval_threshold: 2,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.approve_payout(), Ok(()));
+            assert_eq!(button.get_approval_count(), 1);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_approval_count(), 0);
+        }
+
+        #[ink::test]
+        fn block_number_countdown_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                perpetual: true,
+                use_block_number: true,
+                countdown_blocks: 3,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_countdown(), 3);
+
+            // WHEN
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+
+            // THEN
+            assert_eq!(button.get_countdown(), 1);
+            assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(button.get_countdown(), 0);
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn time_skew_works() {
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10_000);
+            let button = TheButton::new(86400 * 1000, 1000);
+
+            assert_eq!(button.time_skew(10_000), 0);
+            assert_eq!(button.time_skew(7_500), 2_500);
+            assert_eq!(button.time_skew(12_000), -2_000);
+        }
+
+        #[ink::test]
+        fn payout_requires_min_unique_players() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 3_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                min_unique_players: 2,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN
+            // the same account presses twice
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // THEN
+            assert_eq!(button.get_unique_player_count(), 1);
+            assert_eq!(button.payout(), Err(Error::NotEnoughPlayers));
+
+            // WHEN
+            // a second account joins
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2 * 86400 * 1000);
+
+            // THEN
+            assert_eq!(button.get_unique_player_count(), 2);
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn unique_players_are_counted_per_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                min_unique_players: 2,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // WHEN
+            // only one of the players of the first round joins the second
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2 * 86400 * 1000);
+
+            // THEN
+            assert_eq!(button.get_round(), 2);
+            assert_eq!(button.get_unique_player_count(), 1);
+            assert_eq!(button.payout(), Err(Error::NotEnoughPlayers));
+            assert!(button.check_invariants());
+        }
+
+        #[ink::test]
+        fn payout_preference_without_router_pays_natively() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            button.set_payout_preference(true);
+            assert!(button.get_payout_preference(accounts.bob));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_balance_after - bob_balance, 1_000_000);
+
+            button.set_payout_preference(false);
+            assert!(!button.get_payout_preference(accounts.bob));
+        }
+
+        #[ink::test]
+        fn times_dethroned_counts_lost_leads() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            for caller in [accounts.bob, accounts.bob, accounts.charlie, accounts.bob, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_times_dethroned(accounts.bob), 2);
+            assert_eq!(button.get_times_dethroned(accounts.charlie), 1);
+            assert_eq!(button.get_times_dethroned(accounts.django), 0);
+            // the creator never pressed, so it was never dethroned
+            assert_eq!(button.get_times_dethroned(accounts.alice), 0);
+        }
+
+        #[ink::test]
+        fn anti_snipe_extends_late_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                anti_snipe_window: 60 * 1000,
+                anti_snipe_extension: 300 * 1000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            // a press well before the deadline
+            let early = 1000;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(early);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_effective_deadline(), early + 86400 * 1000);
+
+            // WHEN
+            // a press in the last seconds before the deadline
+            let late = early + 86400 * 1000 - 10 * 1000;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(late);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_effective_deadline(), late + 86400 * 1000 + 300 * 1000);
+            assert_eq!(button.get_countdown(), 86400 * 1000 + 300 * 1000);
+        }
+
+        #[ink::test]
+        fn get_presses_for_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            for caller in [accounts.bob, accounts.charlie, accounts.bob] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // WHEN
+            let presses = button.get_presses_for(vec![accounts.charlie, accounts.eve, accounts.bob]);
+
+            // THEN
+            assert_eq!(presses, Ok(vec![1, 0, 2]));
+            assert_eq!(
+                button.get_presses_for(vec![accounts.bob; MAX_BATCH_ACCOUNTS + 1]),
+                Err(Error::TooManyAccounts)
+            );
+        }
+
+        #[ink::test]
+        fn payout_requires_min_hold_blocks() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                min_hold_blocks: 2,
+                ..GameConfig::new(1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            // the countdown passes within the same block
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+
+            // THEN
+            assert_eq!(button.payout(), Err(Error::HoldTooShort));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(button.payout(), Err(Error::HoldTooShort));
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn payout_proof_reconciles_to_balance() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                fee_bps: 333,
+                winner_share_bps: 6_667,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            for (caller, value) in [(accounts.bob, 1_037_000), (accounts.charlie, 1_111_000), (accounts.django, 1_999_000)] {
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(caller, value);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(value);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // WHEN
+            let proof = button.payout_proof();
+
+            // THEN
+            assert_eq!(proof.balance, 1_037_000 + 1_111_000 + 1_999_000);
+            assert_eq!(proof.fees, 34_532 + 36_996 + 66_566);
+            assert_eq!(proof.reserved, 0);
+            assert_eq!(proof.pot, proof.balance - proof.fees);
+            assert_eq!(proof.winner_amount, bps_of(proof.pot, 6_667));
+            assert_eq!(proof.residual, bps_of(proof.pot, 3_333));
+            assert_eq!(proof.dust, 1);
+            assert_eq!(proof.reserved + proof.fees + proof.winner_amount + proof.dust + proof.residual, proof.balance);
+
+            // the payout settles exactly as the proof says
+            let django_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+            let django_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django).unwrap();
+            assert_eq!(django_balance_after - django_balance, proof.winner_amount);
+            assert_eq!(button.get_balance(), proof.residual + proof.dust);
+        }
+
+        #[ink::test]
+        fn self_press_cooldown_blocks_back_to_back_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                self_press_cooldown: 60 * 1000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // the creator is not blocked by the construction
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            // pressing again within the cooldown is blocked
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(30 * 1000);
+            assert_eq!(button.press(Balance::MAX), Err(Error::SelfPressCooldown));
+
+            // another account can press at any time
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // after another account pressed, the first one is eligible again
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // pressing again after the cooldown is allowed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(90 * 1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+        }
+
+        #[ink::test]
+        fn leaderboard_keeps_top_pressers_sorted() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                leaderboard_size: 2,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            let mut press_as = |caller| {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                button.get_leaderboard()
+            };
+
+            // WHEN / THEN
+            assert_eq!(press_as(accounts.bob), vec![(accounts.bob, 1)]);
+            assert_eq!(press_as(accounts.charlie), vec![(accounts.bob, 1), (accounts.charlie, 1)]);
+
+            // a tie with the lowest entry does not evict it
+            assert_eq!(press_as(accounts.django), vec![(accounts.bob, 1), (accounts.charlie, 1)]);
+
+            // more presses than the lowest entry evicts it
+            assert_eq!(press_as(accounts.django), vec![(accounts.django, 2), (accounts.bob, 1)]);
+
+            // existing entries move up
+            assert_eq!(press_as(accounts.bob), vec![(accounts.django, 2), (accounts.bob, 2)]);
+            assert_eq!(press_as(accounts.bob), vec![(accounts.bob, 3), (accounts.django, 2)]);
+        }
+
+        #[ink::test]
+        fn rank_follows_leaderboard() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                leaderboard_size: 2,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            for caller in [accounts.bob, accounts.charlie, accounts.charlie, accounts.django] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_rank(accounts.charlie), Some(1));
+            assert_eq!(button.get_rank(accounts.bob), Some(2));
+            // django pressed but did not make it onto the full leaderboard
+            assert_eq!(button.get_rank(accounts.django), None);
+            assert_eq!(button.get_rank(accounts.eve), None);
+        }
+
+        #[ink::test]
+        fn press_within_reopen_window_revives_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                reopen_window: Some(3600 * 1000),
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            // a press half an hour after the deadline revives the round
+            let revived_at = 86400 * 1000 + 1800 * 1000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(revived_at);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_countdown(), 86400 * 1000);
+            assert_eq!(button.get_balance(), 2000);
+
+            // WHEN
+            // a press after the reopen window of the revived round is rejected
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(revived_at + 86400 * 1000 + 3600 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // THEN
+            assert_eq!(button.press(Balance::MAX), Err(Error::RoundOver));
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn payout_goes_to_registered_destination() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // bob sends his winnings to eve, charlie has no destination
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            button.set_payout_destination(accounts.eve);
+            assert_eq!(button.get_payout_destination(accounts.bob), accounts.eve);
+            assert_eq!(button.get_payout_destination(accounts.charlie), accounts.charlie);
+
+            for (round, winner, recipient) in [(1, accounts.bob, accounts.eve), (2, accounts.charlie, accounts.charlie)] {
+                // GIVEN
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(winner);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                let recipient_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(recipient).unwrap();
+
+                // WHEN
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(round * 86400 * 1000);
+                assert_eq!(button.payout(), Ok(()));
+
+                // THEN
+                let recipient_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(recipient).unwrap();
+                assert_eq!(recipient_balance_after - recipient_balance, 1_000_000);
+            }
+        }
+
+        #[ink::test]
+        fn claim_split_distributes_winnings() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            let recipients = [accounts.charlie, accounts.django, accounts.eve];
+            let balances_before: Vec<Balance> = recipients
+                .iter()
+                .map(|&account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap())
+                .collect();
+
+            // WHEN / THEN
+            // only the winner can claim
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.claim_split(vec![(accounts.charlie, 10_000)]), Err(Error::NotWinner));
+
+            // the basis points have to add up to 100%
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.claim_split(vec![(accounts.charlie, 5_000)]), Err(Error::InvalidSplit));
+            assert_eq!(button.claim_split(vec![]), Err(Error::InvalidSplit));
+
+            let split = vec![(accounts.charlie, 5_000), (accounts.django, 3_000), (accounts.eve, 2_000)];
+            assert_eq!(button.claim_split(split), Ok(()));
+
+            for (account, (before, expected)) in recipients.iter().zip(balances_before.iter().zip([500_000, 300_000, 200_000])) {
+                let after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(*account).unwrap();
+                assert_eq!(after - before, expected);
+            }
+        }
+
+        #[ink::test]
+        fn claim_split_pays_payout_destinations() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            button.set_payout_destination(accounts.frank);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            let charlie_before = balance(accounts.charlie);
+            let frank_before = balance(accounts.frank);
+
+            // WHEN
+            assert_eq!(button.claim_split(vec![(accounts.charlie, 10_000)]), Ok(()));
+
+            // THEN
+            // charlie's share goes to their payout destination
+            assert_eq!(balance(accounts.charlie), charlie_before);
+            assert_eq!(balance(accounts.frank) - frank_before, 1_000_000);
+        }
+
+        #[ink::test]
+        fn simulate_payout_matches_payout() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                fee_bps: 500,
+                winner_share_bps: 9_000,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_100_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            // before the deadline the simulation fails like the payout
+            assert_eq!(button.simulate_payout(), Err(Error::CountdownNotPassed));
+            assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            let (winner, amount) = button.simulate_payout().unwrap();
+            assert_eq!(winner, accounts.bob);
+            assert_eq!(button.get_balance(), 1_000_000);
+
+            let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(button.payout(), Ok(()));
+            let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_balance_after - bob_balance, amount);
+        }
+
+        #[ink::test]
+        fn payout_keeps_existential_deposit() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                existential_deposit: 2_000_000,
+                ..GameConfig::new(86400 * 1000, 0)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // a balance just above, at and below the existential deposit
+            // the round is not reset for free once nothing but the existential deposit is left
+            for (round, balance, expected_prize) in [(1, 2_000_001, 1), (2, 2_000_000, 0), (3, 1_500_000, 0)] {
+                // WHEN
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, balance);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(round * 86400 * 1000);
+                let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+                let expected = if expected_prize > 0 { Ok(()) } else { Err(Error::NothingToPayout) };
+                assert_eq!(button.payout(), expected);
+
+                // THEN
+                let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+                assert_eq!(bob_balance_after - bob_balance, expected_prize);
+                assert_eq!(button.get_balance(), balance - expected_prize);
+            }
+        }
+
+        #[ink::test]
+        fn time_since_last_press_increases() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.get_time_since_last_press(), 0);
+
+            // WHEN / THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4000);
+            assert_eq!(button.get_time_since_last_press(), 3000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9000);
+            assert_eq!(button.get_time_since_last_press(), 8000);
+
+            // a press restarts it
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_time_since_last_press(), 0);
+        }
+
+        #[ink::test]
+        fn whitelist_restricts_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.set_whitelist_enabled(true), Ok(()));
+            assert_eq!(button.add_to_whitelist(accounts.bob), Ok(()));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            // whitelisted accounts can press
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // others cannot
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Err(Error::NotWhitelisted));
+
+            // removing an account from the whitelist locks it out
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.remove_from_whitelist(accounts.bob), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Err(Error::NotWhitelisted));
+
+            // disabling the whitelist opens the game to everyone
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.set_whitelist_enabled(false), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+        }
+
+        #[ink::test]
+        fn whitelist_is_owner_only() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.set_whitelist_enabled(true), Err(Error::NotOwner));
+            assert_eq!(button.add_to_whitelist(accounts.bob), Err(Error::NotOwner));
+            assert_eq!(button.remove_from_whitelist(accounts.bob), Err(Error::NotOwner));
+            assert!(!button.is_whitelist_enabled());
+            assert!(!button.is_whitelisted(accounts.bob));
+        }
+
+        #[ink::test]
+        fn banned_accounts_cannot_press() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            assert_eq!(button.ban(accounts.bob), Ok(()));
+
+            // THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Err(Error::Banned));
+            assert_eq!(button.ban(accounts.charlie), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.unban(accounts.bob), Ok(()));
+
+            // THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert!(!button.is_banned(accounts.bob));
+        }
+
+        #[ink::test]
+        fn banned_leader_still_wins() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.ban(accounts.bob), Ok(()));
+
+            // THEN
+            assert_eq!(button.simulate_payout(), Ok((accounts.bob, 1_000_000)));
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn get_config_matches_constructor() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            let button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(
+                button.get_config(),
+                GameConfig {
+                    beneficiary: Some(accounts.alice),
+                    ..GameConfig::new(86400 * 1000, 1000)
+                }
+            );
+
+            let config = GameConfig {
+                fee_bps: 250,
+                beneficiary: Some(accounts.eve),
+                perpetual: true,
+                ..GameConfig::new(3600 * 1000, 500)
+            };
+            let button = TheButton::with_config(config.clone()).unwrap();
+            assert_eq!(button.get_config(), config);
+        }
+
+        #[ink::test]
+        fn jackpot_tiers_unlock_with_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                jackpot_tiers: vec![(2, 1_500_000), (4, 10_000_000)],
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 4_000_000);
+            assert_eq!(button.get_current_tier(), None);
+            assert_eq!(button.get_tier_shortfall(), 0);
+
+            // WHEN / THEN
+            let expected = [
+                (1, None, 0),
+                (2, Some((2, 1_500_000)), 0),
+                (3, Some((2, 1_500_000)), 0),
+                (4, Some((4, 10_000_000)), 6_000_000),
+            ];
+            for (presses, tier, shortfall) in expected {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_000_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert_eq!(button.get_press_count(), presses);
+                assert_eq!(button.get_current_tier(), tier);
+                assert_eq!(button.get_tier_shortfall(), shortfall);
+            }
+        }
+
+        #[ink::test]
+        fn pot_extensions_apply_once() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                pot_extensions: vec![(250, 500), (400, 2000)],
+                ..GameConfig::new(1000, 100)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN / THEN
+            // each press is given as (timestamp, value, effective deadline)
+            let expected = [
+                (0, 100, 1000),
+                (10, 200, 10 + 1000 + 500),
+                (20, 100, 20 + 1000 + 2000),
+                (30, 100, 30 + 1000),
+                (40, 500, 40 + 1000),
+            ];
+            for (timestamp, value, deadline) in expected {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(value);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert_eq!(button.get_effective_deadline(), deadline);
+            }
+        }
+
+        #[ink::test]
+        fn pot_extensions_fire_again_next_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                pot_extensions: vec![(200, 500)],
+                ..GameConfig::new(1000, 100)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_effective_deadline(), 1500);
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1500);
+            assert_eq!(button.payout(), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_effective_deadline(), 1500 + 1000 + 500);
+        }
+
+        #[ink::test]
+        fn with_config_rejects_unsorted_pot_extensions() {
+            let config = GameConfig {
+                pot_extensions: vec![(400, 2000), (250, 500)],
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn with_config_rejects_unsorted_jackpot_tiers() {
+            let config = GameConfig {
+                jackpot_tiers: vec![(4, 10_000), (2, 1500)],
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn restart_after_payout_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(90000 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.restart(), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let result = button.restart();
+
+            // THEN
+            assert_eq!(result, Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.alice);
+            assert_eq!(button.get_last_press_timestamp(), 90000 * 1000);
+            assert_eq!(button.get_countdown(), 86400 * 1000);
+            assert_eq!(button.get_press_count(), 1);
+        }
+
+        #[ink::test]
+        fn restart_rejects_active_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.restart(), Err(Error::CountdownNotPassed));
+
+            // an unpaid winner keeps their claim
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.restart(), Err(Error::GameAlreadyPlayed));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn restart_keeps_round_and_theme() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut one_shot = TheButton::new(86400 * 1000, 1000);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.set_round_theme(String::from("next")), Ok(()));
+
+            // WHEN
+            let one_shot_result = one_shot.restart();
+            let result = button.restart();
+
+            // THEN
+            assert_eq!(one_shot_result, Err(Error::NotPerpetual));
+            assert_eq!(result, Ok(()));
+            assert_eq!(button.get_round(), 1);
+            assert_eq!(button.get_round_theme(), String::new());
+        }
+
+        #[ink::test]
+        fn countdown_breakdown_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let button = TheButton::new(2 * 86400 * 1000, 1000);
+
+            // WHEN / THEN
+            assert_eq!(button.get_countdown_breakdown(), (2, 0, 0, 0));
+
+            // just under one day
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000 + 1000);
+            assert_eq!(button.get_countdown_breakdown(), (0, 23, 59, 59));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000 + 3723 * 1000);
+            assert_eq!(button.get_countdown_breakdown(), (0, 22, 57, 57));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * 86400 * 1000);
+            assert_eq!(button.get_countdown_breakdown(), (0, 0, 0, 0));
+        }
+
+        #[ink::test]
+        fn min_press_interval_throttles_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                min_press_interval: 6 * 1000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // the first press is always allowed
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            // any account pressing within the interval is rejected
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Err(Error::PressTooSoon));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+
+            // pressing after the interval is allowed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(6 * 1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.charlie);
+        }
+
+        #[ink::test]
+        fn deadline_advances_with_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.get_deadline(), 86400 * 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_deadline(), 1000 + 86400 * 1000);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_deadline(), 5000 + 86400 * 1000);
+
+            // the deadline saturates instead of overflowing
+            let button = TheButton::new(u64::MAX, 1000);
+            assert_eq!(button.get_deadline(), u64::MAX);
+        }
+
+        #[ink::test]
+        fn payout_pays_consolation_to_recent_pressers() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                consolation_count: 2,
+                consolation_bps: 1000,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // charlie presses twice, so the two most recent distinct pressers before eve are django and charlie
+            for caller in [accounts.bob, accounts.charlie, accounts.django, accounts.charlie, accounts.eve] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(caller, 1_000_000);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_000_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+            let proof = button.payout_proof();
+            assert_eq!(proof.consolation_each, 250_000);
+            assert_eq!(proof.consolation, 500_000);
+            assert_eq!(proof.winner_amount, 4_500_000);
+            assert_eq!(
+                proof.reserved + proof.fees + proof.winner_amount + proof.consolation + proof.dust + proof.residual,
+                proof.balance
+            );
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            assert_eq!(balance(accounts.eve), 4_500_000);
+            assert_eq!(balance(accounts.charlie), 250_000);
+            assert_eq!(balance(accounts.django), 250_000);
+            assert_eq!(balance(accounts.bob), 0);
+            assert_eq!(button.get_balance(), 0);
+        }
+
+        #[ink::test]
+        fn pull_payments_credit_consolations() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                pull_payments: true,
+                consolation_count: 2,
+                consolation_bps: 1000,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            for caller in [accounts.bob, accounts.charlie, accounts.django, accounts.eve] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(caller, 1_000_000);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_000_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            // the consolations are credited like the prize instead of being transferred
+            let balance = |account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap();
+            assert_eq!(balance(accounts.charlie), 0);
+            assert_eq!(balance(accounts.django), 0);
+            assert_eq!(button.get_withdrawable(accounts.charlie), 200_000);
+            assert_eq!(button.get_withdrawable(accounts.django), 200_000);
+            assert_eq!(button.get_withdrawable(accounts.eve), 3_600_000);
+            assert_eq!(button.get_balance(), 4_000_000);
+            assert!(button.check_invariants());
+        }
+
+        #[ink::test]
+        fn escalating_price_stops_at_max_price() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                min_increment: Balance::MAX / 2,
+                max_price: 3000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_current_price(), 1000);
+
+            // WHEN
+            for caller in [accounts.bob, accounts.charlie, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(button.get_current_price());
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+
+                // THEN
+                assert_eq!(button.get_current_price(), 3000);
+            }
+
+            // overpaying does not push the price past the cap either
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(Balance::MAX);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_current_price(), 3000);
+        }
+
+        #[ink::test]
+        fn with_config_rejects_max_price_below_minimum() {
+            let config = GameConfig {
+                max_price: 999,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn transfer_ownership_emits_event() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.transfer_ownership(accounts.bob), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.transfer_ownership(accounts.bob), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_owner(), accounts.bob);
+            assert_eq!(button.set_round_theme(String::from("mine")), Err(Error::NotOwner));
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 1);
+            // the signature topic plus both accounts
+            assert_eq!(emitted_events[0].topics.len(), 3);
+            let event = <OwnershipTransferred as ink::scale::Decode>::decode(&mut &emitted_events[0].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.previous_owner, accounts.alice);
+            assert_eq!(event.new_owner, accounts.bob);
+        }
+
+        #[ink::test]
+        fn payout_access_restricts_callers() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let cases = [
+                (PayoutAccess::Anyone, None, accounts.charlie),
+                (PayoutAccess::WinnerOnly, Some(accounts.charlie), accounts.bob),
+                (PayoutAccess::OwnerOnly, Some(accounts.bob), accounts.alice),
+            ];
+            for (payout_access, unauthorized, authorized) in cases {
+                // GIVEN
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+                let config = GameConfig {
+                    perpetual: true,
+                    payout_access,
+                    ..GameConfig::new(86400 * 1000, 1000)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+                // WHEN / THEN
+                if let Some(caller) = unauthorized {
+                    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                    assert_eq!(button.simulate_payout(), Err(Error::NotAuthorizedToPayout));
+                    assert_eq!(button.payout(), Err(Error::NotAuthorizedToPayout));
+                }
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(authorized);
+                assert_eq!(button.payout(), Ok(()));
+            }
+        }
+
+        #[ink::test]
+        fn claim_split_respects_payout_access() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                payout_access: PayoutAccess::OwnerOnly,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // WHEN
+            let result = button.claim_split(vec![(accounts.bob, 10_000)]);
+
+            // THEN
+            assert_eq!(result, Err(Error::NotAuthorizedToPayout));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn payout_terminates_to_winner() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                existential_deposit: 100,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // WHEN
+            // a third party triggers the payout
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let should_terminate = move || button.payout().unwrap();
+
+            // THEN
+            // the reserved existential deposit goes to the winner as well
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.bob,
+                100,
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie),
+                Ok(1000)
+            );
+        }
+
+        #[ink::test]
+        fn version_matches_constant() {
+            let button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.version(), CONTRACT_VERSION);
+        }
+
+        #[ink::test]
+        fn would_win_at_projects_winner() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.would_win_at(0), None);
+            assert_eq!(button.would_win_at(1000 + 86400 * 1000 - 1), None);
+            assert_eq!(button.would_win_at(1000 + 86400 * 1000), Some(accounts.bob));
+            assert_eq!(button.would_win_at(u64::MAX), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn press_fee_is_forwarded_to_beneficiary() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                beneficiary: Some(accounts.django),
+                press_fee: 50_000,
+                ..GameConfig::new(86400 * 1000, 1_000_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.required_gross_payment(), 1_050_000);
+
+            // WHEN / THEN
+            // the pot has to be covered on top of the press fee
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1_049_999);
+            assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(49_999);
+            assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_050_000);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_050_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.django),
+                Ok(50_000)
+            );
+            assert_eq!(button.get_balance(), 1_000_000);
+        }
+
+        #[ink::test]
+        fn additional_games_are_isolated() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            let first = button.create_game(3600 * 1000, 100).unwrap();
+            let second = button.create_game(7200 * 1000, 200).unwrap();
+            assert_ne!(first, second);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.create_game(3600 * 1000, 100), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press_game(first), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press_game(second), Err(Error::InsertCoinToContinue));
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(button.press_game(second), Ok(()));
+            assert_eq!(button.press_game(42), Err(Error::GameNotFound));
+
+            // THEN
+            let game = button.get_game(first).unwrap();
+            assert_eq!(game.last_press_caller, accounts.bob);
+            assert_eq!(game.pot, 100);
+            let game = button.get_game(second).unwrap();
+            assert_eq!(game.last_press_caller, accounts.charlie);
+            assert_eq!(game.pot, 200);
+            // the main game is not affected
+            assert_eq!(button.get_last_press_caller(), accounts.alice);
+            assert_eq!(button.get_press_count(), 0);
+            assert_eq!(button.payout_proof().pot, 0);
+
+            // the first game can be paid out while the second is still running
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3600 * 1000);
+            assert_eq!(button.payout_game(second), Err(Error::CountdownNotPassed));
+            assert_eq!(button.payout_game(first), Ok(()));
+            assert_eq!(button.get_game(first), None);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(1000)
+            );
+            assert_eq!(button.get_game(second).unwrap().pot, 200);
+            assert_eq!(button.get_balance(), 200);
+        }
+
+        #[ink::test]
+        fn active_games_block_final_payout() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            let game_id = button.create_game(3600 * 1000, 100).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press_game(game_id), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+
+            // THEN
+            // terminating the contract would take the pot of the game with it
+            assert_eq!(button.payout(), Err(Error::GameAlreadyPlayed));
+            assert_eq!(button.payout_game(game_id), Ok(()));
+            let should_terminate = move || button.payout().unwrap();
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.bob,
+                0,
+            );
+        }
+
+        #[ink::test]
+        fn game_press_is_checked_and_charged_like_a_press() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                fee_bps: 1000,
+                start_time: 1000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            let game_id = button.create_game(3600 * 1000, 900).unwrap();
+            assert_eq!(button.ban(accounts.django), Ok(()));
+
+            // WHEN / THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press_game(game_id), Err(Error::GameNotStarted));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(button.press_game(game_id), Err(Error::Banned));
+
+            // the house fee is taken before the game's minimum raise is checked
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(998);
+            assert_eq!(button.press_game(game_id), Err(Error::InsertCoinToContinue));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press_game(game_id), Ok(()));
+            assert_eq!(button.get_game(game_id).unwrap().pot, 900);
+            assert_eq!(button.accrued_fees, 100);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = <GamePressed as ink::scale::Decode>::decode(&mut &emitted_events.last().unwrap().data[..])
+                .expect("invalid event data");
+            assert_eq!(event.game_id, game_id);
+            assert_eq!(event.caller, accounts.bob);
+            assert_eq!(event.amount, 900);
+            assert_eq!(event.new_deadline, 1000 + 3600 * 1000);
+        }
+
+        #[ink::test]
+        fn game_payout_respects_payout_access() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                payout_access: PayoutAccess::WinnerOnly,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            let game_id = button.create_game(3600 * 1000, 100).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press_game(game_id), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3600 * 1000);
+
+            // WHEN / THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.payout_game(game_id), Err(Error::NotAuthorizedToPayout));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.payout_game(game_id), Ok(()));
+            assert_eq!(button.get_game(game_id), None);
+        }
+
+        #[ink::test]
+        fn paid_out_games_are_not_active() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_active_games(), Vec::<u32>::new());
+            let games = [
+                button.create_game(3600 * 1000, 0).unwrap(),
+                button.create_game(60 * 1000, 0).unwrap(),
+                button.create_game(7200 * 1000, 0).unwrap(),
+            ];
+            assert_eq!(button.get_active_games(), games.to_vec());
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(60 * 1000);
+            assert_eq!(button.payout_game(games[1]), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_active_games(), vec![games[0], games[2]]);
+            assert_eq!(button.payout_game(games[1]), Err(Error::GameNotFound));
+        }
+
+        #[ink::test]
+        fn press_rejects_price_raised_after_quote() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                min_increment: 100,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(1000), Ok(()));
+
+            // WHEN
+            // charlie quotes the price, but django presses first and raises it
+            let quote = button.required_gross_payment();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(quote);
+            assert_eq!(button.press(quote), Ok(()));
+
+            // THEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(button.required_gross_payment());
+            assert_eq!(button.press(quote), Err(Error::PriceChanged));
+            assert_eq!(button.get_last_press_caller(), accounts.django);
+            assert_eq!(button.press(button.required_gross_payment()), Ok(()));
+        }
+
+        #[ink::test]
+        fn total_volume_accumulates_across_rounds() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                fee_bps: 1000,
+                ..GameConfig::new(86400 * 1000, 900)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN
+            for (caller, value) in [(accounts.bob, 1000), (accounts.charlie, 2000), (accounts.bob, 1000)] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(value);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            // only the amounts added to the pot count, not the house fees
+            assert_eq!(button.get_total_volume(), 3600);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+            assert_eq!(button.get_total_volume(), 3600);
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_requires_abandonment() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                abandonment_grace: Some(7 * 86400 * 1000),
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            // the round is live
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.emergency_withdraw(), Err(Error::CountdownNotPassed));
+
+            // the winner can still claim within the grace window
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 86400 * 1000 - 1);
+            assert_eq!(button.emergency_withdraw(), Err(Error::CountdownNotPassed));
+
+            // only the owner can withdraw
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.emergency_withdraw(), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let should_terminate = move || button.emergency_withdraw().unwrap();
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                1000,
+            );
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_pays_fees_to_beneficiary() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                abandonment_grace: Some(7 * 86400 * 1000),
+                fee_bps: 1000,
+                beneficiary: Some(accounts.charlie),
+                ..GameConfig::new(86400 * 1000, 900)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            let beneficiary_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie).unwrap();
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(8 * 86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let should_terminate = move || button.emergency_withdraw().unwrap();
+
+            // THEN
+            // the owner only receives the abandoned pot
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                900,
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie),
+                Ok(beneficiary_balance + 100)
+            );
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_is_disabled_by_default() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(u64::MAX);
+            assert_eq!(button.emergency_withdraw(), Err(Error::CountdownNotPassed));
+        }
+
+        #[ink::test]
+        fn seconds_remaining_rounds_up() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let button = TheButton::new(10 * 1000, 1000);
+
+            // WHEN / THEN
+            assert_eq!(button.get_seconds_remaining(), 10);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1);
+            assert_eq!(button.get_seconds_remaining(), 10);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.get_seconds_remaining(), 9);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9999);
+            assert_eq!(button.get_seconds_remaining(), 1);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10 * 1000);
+            assert_eq!(button.get_seconds_remaining(), 0);
+        }
+
+        #[ink::test]
+        fn press_with_message_stores_message() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press_with_message(Balance::MAX, b"GGWP".to_vec()), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_last_press_message(), b"GGWP".to_vec());
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            let event = <ButtonPressed as ink::scale::Decode>::decode(&mut &emitted_events[1].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.caller, accounts.bob);
+            assert_eq!(event.amount, 1000);
+            assert_eq!(event.message, b"GGWP".to_vec());
+
+            // an over-length message is rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press_with_message(Balance::MAX, vec![b'a'; 65]), Err(Error::MessageTooLong));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+
+            // a plain press clears the message
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_last_press_message(), Vec::<u8>::new());
+        }
+
+        #[ink::test]
+        fn countdown_shrinks_down_to_floor() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                countdown_step: 4000,
+                countdown_floor: 5000,
+                ..GameConfig::new(15000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_active_countdown_duration(), 15000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            let expected = [
+                (accounts.bob, 11000),
+                (accounts.charlie, 7000),
+                (accounts.bob, 5000),
+                (accounts.charlie, 5000),
+            ];
+            for (caller, duration) in expected {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert_eq!(button.get_active_countdown_duration(), duration);
+                assert_eq!(button.get_countdown(), duration);
+            }
+            assert_eq!(button.get_config().countdown_duration, 15000);
+        }
+
+        #[ink::test]
+        fn balance_breakdown_separates_fees() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                fee_bps: 500,
+                ..GameConfig::new(86400 * 1000, 900_000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_balance_breakdown(), (0, 0));
+
+            // WHEN
+            for caller in [accounts.bob, accounts.charlie, accounts.bob] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(caller, 1_000_000);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_000_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_balance_breakdown(), (2_850_000, 150_000));
+            assert_eq!(button.get_balance(), 3_000_000);
+        }
+
+        #[ink::test]
+        fn check_invariants_detects_corruption() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                fee_bps: 1000,
+                max_price: 2000,
+                ..GameConfig::new(86400 * 1000, 900)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert!(button.check_invariants());
+
+            // WHEN / THEN
+            button.unique_player_count = 2;
+            assert!(!button.check_invariants());
+            button.unique_player_count = 1;
+
+            button.accrued_fees = 1001;
+            assert!(!button.check_invariants());
+            button.accrued_fees = 100;
+
+            button.config.max_price = 800;
+            assert!(!button.check_invariants());
+            button.config.max_price = 2000;
+            assert!(button.check_invariants());
+        }
+
+        #[ink::test]
+        fn creator_seed_is_refunded_on_payout() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                refund_creator_seed: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(5000);
+            let mut button = TheButton::with_config(config).unwrap();
+            let creator_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice).unwrap();
+            // the seed stays with the creator when the ownership changes hands
+            assert_eq!(button.transfer_ownership(accounts.charlie), Ok(()));
+            let owner_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            let proof = button.payout_proof();
+            assert_eq!(proof.seed, 5000);
+            assert_eq!(proof.pot, 1000);
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.alice),
+                Ok(creator_balance + 5000)
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.charlie),
+                Ok(owner_balance)
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(1000)
+            );
+            assert_eq!(button.payout_proof().seed, 0);
+        }
+
+        #[ink::test]
+        fn recent_press_times_keep_latest_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                press_history_size: 3,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            for (index, caller) in [accounts.bob, accounts.charlie, accounts.bob, accounts.charlie, accounts.bob]
+                .into_iter()
+                .enumerate()
+            {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(index as u64 * 1000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_recent_press_times(), vec![2000, 3000, 4000]);
+        }
+
+        #[ink::test]
+        fn press_rate_follows_recorded_cadence() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                press_history_size: 4,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.get_press_rate_per_hour(), 0);
+
+            // WHEN a press every 10 minutes
+            for (index, caller) in [accounts.bob, accounts.charlie, accounts.bob, accounts.charlie, accounts.bob]
+                .into_iter()
+                .enumerate()
+            {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(index as u64 * 600_000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                if index == 0 {
+                    assert_eq!(button.get_press_rate_per_hour(), 0);
+                }
+            }
+
+            // THEN
+            assert_eq!(button.get_press_rate_per_hour(), 6);
+        }
+
+        #[ink::test]
+        fn total_won_accumulates_across_rounds() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 100)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN
+            // bob wins two rounds and charlie one
+            let rounds = [(accounts.bob, 300), (accounts.charlie, 200), (accounts.bob, 500)];
+            for (round, (winner, value)) in rounds.into_iter().enumerate() {
+                let start = round as u64 * 86400 * 1000;
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(winner);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(value);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start + 86400 * 1000);
+                assert_eq!(button.payout(), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_total_won(accounts.bob), 800);
+            assert_eq!(button.get_total_won(accounts.charlie), 200);
+            assert_eq!(button.get_total_won(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn first_press_per_block_wins() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            let config = GameConfig {
+                first_press_per_block_wins: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // the first press of the round is allowed in the block of the construction
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            // a second press with the same block and timestamp is rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Err(Error::AlreadyPressedThisBlock));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+
+            // with the same timestamp, the press in the later block wins
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.charlie);
+        }
+
+        #[ink::test]
+        fn payout_requires_min_pot() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                min_payout_pot: 1500,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Err(Error::PotTooSmall));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(500);
+            button.donate();
+            assert_eq!(button.payout(), Ok(()));
+            assert_eq!(button.get_balance(), 0);
+        }
+
+        #[ink::test]
+        fn average_press_amount_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.get_average_press_amount(), 0);
+
+            // WHEN
+            for (caller, value) in [(accounts.bob, 1000), (accounts.charlie, 2500), (accounts.bob, 1500)] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(value);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_average_press_amount(), 1666);
+        }
+
+        #[ink::test]
+        fn press_for_records_presser() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.add_relayer(accounts.eve), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.add_relayer(accounts.bob), Err(Error::NotOwner));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            // non-relayers cannot impersonate other accounts
+            assert_eq!(button.press_for(Balance::MAX, accounts.charlie), Err(Error::NotRelayer));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(button.press_for(Balance::MAX, accounts.charlie), Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.charlie);
+            assert_eq!(button.get_presses(accounts.charlie), 1);
+            assert_eq!(button.get_presses(accounts.eve), 0);
+
+            // the rules of a press apply to the presser
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.ban(accounts.django), Ok(()));
+            assert_eq!(button.remove_relayer(accounts.eve), Ok(()));
+            assert!(!button.is_relayer(accounts.eve));
+            assert_eq!(button.add_relayer(accounts.frank), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert_eq!(button.press_for(Balance::MAX, accounts.django), Err(Error::Banned));
+        }
+
+        #[ink::test]
+        fn payout_donates_to_charity() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                charity: Some(accounts.frank),
+                charity_bps: 1500,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.frank),
+                Ok(150)
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(850)
+            );
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = <CharityDonated as ink::scale::Decode>::decode(&mut &emitted_events[2].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.charity, accounts.frank);
+            assert_eq!(event.amount, 150);
+        }
+
+        #[ink::test]
+        fn with_config_rejects_invalid_charity() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let config = GameConfig {
+                charity_bps: 1500,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+            let config = GameConfig {
+                perpetual: true,
+                charity: Some(accounts.frank),
+                charity_bps: 6000,
+                consolation_count: 1,
+                consolation_bps: 5000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn is_winning_works() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert!(button.is_winning(accounts.bob));
+            assert!(!button.is_winning(accounts.alice));
+            assert!(!button.is_winning(accounts.charlie));
+        }
+
+        #[ink::test]
+        fn leader_can_top_up() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                top_up_min: 100,
+                ..GameConfig::new(86400 * 1000, 500)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // the owner is not the leader before the first press
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.top_up(), Err(Error::NotCurrentLeader));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3600 * 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(99);
+            assert_eq!(button.top_up(), Err(Error::InsertCoinToContinue));
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.top_up(), Ok(()));
+            assert_eq!(button.get_balance(), 600);
+            assert_eq!(button.get_countdown(), 86400 * 1000);
+            assert_eq!(button.get_press_count(), 1);
+
+            // other players cannot top up
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.top_up(), Err(Error::NotCurrentLeader));
+        }
+
+        #[ink::test]
+        fn expired_game_can_be_paid_out_right_away() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            let config = GameConfig {
+                perpetual: true,
+                max_lifetime: 7 * 86400 * 1000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_game_expiry(), Some(1000 + 7 * 86400 * 1000));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(7 * 86400 * 1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert!(!button.is_expired());
+
+            // THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000 + 7 * 86400 * 1000);
+            assert!(button.is_expired());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Err(Error::GameExpired));
+            // the countdown is still running, but the game has ended
+            assert!(button.get_countdown() > 0);
+            assert_eq!(button.simulate_payout().map(|(winner, _)| winner), Ok(accounts.bob));
+            assert_eq!(button.payout(), Ok(()));
+        }
+
+        #[ink::test]
+        fn pull_payments_credit_winnings() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                pull_payments: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            // the winnings are credited, not transferred, and do not count towards the next pot
+            assert_eq!(button.get_withdrawable(accounts.bob), 1000);
+            assert_eq!(button.get_balance(), 1000);
+            assert_eq!(button.payout_proof().pot, 0);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(0)
+            );
+
+            // only the credited account can withdraw, and only once
+            assert_eq!(button.withdraw(), Err(Error::NothingToWithdraw));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.withdraw(), Ok(()));
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(1000)
+            );
+            assert_eq!(button.get_withdrawable(accounts.bob), 0);
+            assert_eq!(button.withdraw(), Err(Error::NothingToWithdraw));
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_rejects_unclaimed_winnings() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                pull_payments: true,
+                abandonment_grace: Some(86400 * 1000),
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // WHEN
+            // nobody presses in the next round until the grace period has passed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3 * 86400 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // THEN
+            // the credited winnings cannot be swept to the owner
+            assert_eq!(button.emergency_withdraw(), Err(Error::UnclaimedWinnings));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.withdraw(), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let should_terminate = move || button.emergency_withdraw().unwrap();
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.alice,
+                0,
+            );
+        }
+
+        #[ink::test]
+        fn with_config_rejects_pull_payments_without_perpetual_mode() {
+            let config = GameConfig {
+                pull_payments: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn time_to_beat_matches_next_countdown() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let fixed = TheButton::new(86400 * 1000, 1000);
+            let config = GameConfig {
+                countdown_step: 4000,
+                countdown_floor: 5000,
+                anti_snipe_window: 2000,
+                anti_snipe_extension: 3000,
+                ..GameConfig::new(15000, 1000)
+            };
+            let mut decaying = TheButton::with_config(config).unwrap();
+
+            // WHEN / THEN
+            assert_eq!(fixed.get_time_to_beat(), 86400 * 1000);
+            assert_eq!(decaying.get_time_to_beat(), 11000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(decaying.press(Balance::MAX), Ok(()));
+            assert_eq!(decaying.get_time_to_beat(), 7000);
+
+            // a press within the anti-sniping window would be extended
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10000);
+            assert_eq!(decaying.get_time_to_beat(), 10000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(decaying.press(Balance::MAX), Ok(()));
+            assert_eq!(decaying.get_countdown(), 10000);
+        }
+
+        #[ink::test]
+        fn time_to_beat_includes_scheduled_countdown_duration() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            assert_eq!(button.set_countdown_duration(3600 * 1000), Ok(()));
+
+            // THEN
+            // the running countdown keeps its deadline, but the next press starts the new duration
+            assert_eq!(button.get_countdown(), 86400 * 1000);
+            assert_eq!(button.get_time_to_beat(), 3600 * 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_countdown(), 3600 * 1000);
+        }
+
+        #[ink::test]
+        fn random_jackpot_adds_fees_to_prize() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                fee_bps: 1000,
+                random_jackpot: true,
+                jackpot_chance_bps: 5000,
+                ..GameConfig::new(1000, 500)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN the roll misses
+            button.set_jackpot_seed([0xFF; 32]);
+            let proof = button.payout_settlement();
+
+            // THEN the fees go to the beneficiary
+            assert_eq!(proof.jackpot_bonus, 0);
+            assert_eq!(proof.fees, 100);
+            assert_eq!(proof.winner_amount, 900);
+
+            // WHEN the roll hits
+            button.set_jackpot_seed([0; 32]);
+            let proof = button.payout_settlement();
+
+            // THEN the fees are added to the prize, but the queries do not tell
+            assert_eq!(proof.jackpot_bonus, 100);
+            assert_eq!(proof.fees, 0);
+            assert_eq!(proof.winner_amount, 1000);
+            assert_eq!(button.payout_proof().jackpot_bonus, 0);
+            assert_eq!(button.payout_proof().winner_amount, 900);
+            assert_eq!(button.simulate_payout(), Ok((accounts.bob, 900)));
+            let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(button.payout(), Ok(()));
+            let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_balance_after - bob_balance, 1000);
+        }
+
+        #[ink::test]
+        fn random_jackpot_requires_opt_in() {
+            let config = GameConfig {
+                jackpot_chance_bps: 100,
+                ..GameConfig::new(1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn display_amounts_use_configured_decimals() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                decimals: 10,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_display_amounts(), (1000, 10));
+            assert_eq!(TheButton::new(86400 * 1000, 1000).get_display_amounts().1, 12);
+        }
+
+        #[ink::test]
+        fn countdown_duration_change_applies_from_next_press() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(10000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.set_countdown_duration(1000), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.set_countdown_duration(20000), Ok(()));
+
+            // THEN the running countdown is unchanged
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4000);
+            assert_eq!(button.get_countdown(), 6000);
+            assert_eq!(button.get_deadline(), 10000);
+
+            // the new duration applies once someone presses
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_countdown(), 20000);
+            assert_eq!(button.get_deadline(), 24000);
+        }
+
+        #[ink::test]
+        fn elapsed_fraction_tracks_countdown() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let button = TheButton::new(10000, 1000);
+
+            // WHEN / THEN
+            assert_eq!(button.get_elapsed_fraction_bps(), 0);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(button.get_elapsed_fraction_bps(), 5000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10000);
+            assert_eq!(button.get_elapsed_fraction_bps(), 10000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(25000);
+            assert_eq!(button.get_elapsed_fraction_bps(), 10000);
+
+            // a zero countdown has always elapsed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(TheButton::new(0, 1000).get_elapsed_fraction_bps(), 10000);
+        }
+
+        #[ink::test]
+        fn press_before_start_time_is_rejected() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                start_time: 5000,
+                ..GameConfig::new(10000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_start_time(), 5000);
+            assert_eq!(button.get_deadline(), 15000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4999);
+            assert_eq!(button.get_countdown(), 10000);
+            assert_eq!(button.press(Balance::MAX), Err(Error::GameNotStarted));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_deadline(), 15000);
+        }
+
+        #[ink::test]
+        fn runner_up_lags_leader_by_one_press() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.get_runner_up(), accounts.alice);
+
+            // WHEN / THEN
+            let pressers = [accounts.bob, accounts.charlie, accounts.django, accounts.bob];
+            let mut leader = accounts.alice;
+            for presser in pressers {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(presser);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert_eq!(button.get_runner_up(), leader);
+                assert_eq!(button.get_last_press_caller(), presser);
+                leader = presser;
+            }
+        }
+
+        #[ink::test]
+        fn events_carry_the_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_round(), 1);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.payout(), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_round(), 2);
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 5);
+            let first_press = <ButtonPressed as ink::scale::Decode>::decode(&mut &emitted_events[1].data[..])
+                .expect("invalid event data");
+            assert_eq!(first_press.round, 1);
+            let reward = <RewardClaimed as ink::scale::Decode>::decode(&mut &emitted_events[2].data[..])
+                .expect("invalid event data");
+            assert_eq!(reward.winner, accounts.bob);
+            assert_eq!(reward.round, 1);
+            assert_eq!(reward.amount, 1000);
+            let second_press = <ButtonPressed as ink::scale::Decode>::decode(&mut &emitted_events[4].data[..])
+                .expect("invalid event data");
+            assert_eq!(second_press.caller, accounts.charlie);
+            assert_eq!(second_press.round, 2);
+        }
+
+        #[ink::test]
+        fn press_above_max_raise_is_rejected() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                max_raise_balance: 2000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut capped = TheButton::with_config(config).unwrap();
+            let mut uncapped = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            // WHEN / THEN
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2001);
+            assert_eq!(capped.press(Balance::MAX), Err(Error::OverMaxRaise));
+            assert_eq!(uncapped.press(Balance::MAX), Ok(()));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            assert_eq!(capped.press(Balance::MAX), Ok(()));
+            assert_eq!(capped.get_last_press_caller(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn top_up_above_max_raise_is_rejected() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                max_raise_balance: 2000,
+                top_up_min: 100,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN / THEN
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2001);
+            assert_eq!(button.top_up(), Err(Error::OverMaxRaise));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(2000);
+            assert_eq!(button.top_up(), Ok(()));
+        }
+
+        #[ink::test]
+        fn podium_splits_prize_between_last_three_pressers() {
+            for (pressers, expected) in [
+                (3, [700, 200, 100]),
+                (2, [800, 200, 0]),
+                (1, [1000, 0, 0]),
+            ] {
+                // GIVEN
+                let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+                let contract = AccountId::from([0xFF; 32]);
+                ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+                let config = GameConfig {
+                    perpetual: true,
+                    podium_bps: Some((7000, 2000, 1000)),
+                    ..GameConfig::new(1000, 100)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+                let all = [accounts.django, accounts.charlie, accounts.bob];
+                let players = &all[3 - pressers..];
+                // the first press brings the pot to 1000, later presses only add to it
+                for (index, &player) in players.iter().enumerate() {
+                    ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(player, 1_000_000);
+                    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(player);
+                    let value = if index == 0 { 1000 - 100 * (pressers as Balance - 1) } else { 100 };
+                    ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(value);
+                    assert_eq!(button.press(Balance::MAX), Ok(()));
+                }
+                let before: Vec<Balance> = all
+                    .iter()
+                    .map(|&account| ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap())
+                    .collect();
+
+                // WHEN
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+                assert_eq!(button.payout(), Ok(()));
+
+                // THEN the winner, the runner-up and the third get their shares
+                let won: Vec<Balance> = all
+                    .iter()
+                    .zip(before)
+                    .map(|(&account, before)| {
+                        ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(account).unwrap() - before
+                    })
+                    .collect();
+                assert_eq!(won, vec![expected[2], expected[1], expected[0]]);
+            }
+        }
+
+        #[ink::test]
+        fn contract_age_grows_with_time() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            let button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.get_contract_age(), 0);
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(65000);
+
+            // THEN
+            assert_eq!(button.get_contract_age(), 60000);
+        }
+
+        #[ink::test]
+        fn reseed_fees_seed_the_next_round() {
+            for reseed_fees in [true, false] {
+                // GIVEN
+                let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+                let contract = AccountId::from([0xFF; 32]);
+                ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.eve, 0);
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+                let config = GameConfig {
+                    perpetual: true,
+                    fee_bps: 1000,
+                    beneficiary: Some(accounts.eve),
+                    reseed_fees,
+                    ..GameConfig::new(1000, 500)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+                ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+                assert_eq!(button.get_reserved_reseed(), if reseed_fees { 100 } else { 0 });
+
+                // WHEN
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+                assert_eq!(button.payout(), Ok(()));
+
+                // THEN the next round starts with the retained fees or an empty pot
+                let beneficiary_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve);
+                if reseed_fees {
+                    assert_eq!(button.get_balance_breakdown(), (100, 0));
+                    assert_eq!(beneficiary_balance, Ok(0));
+                } else {
+                    assert_eq!(button.get_balance_breakdown(), (0, 0));
+                    assert_eq!(beneficiary_balance, Ok(100));
+                }
+            }
+        }
+
+        #[ink::test]
+        fn required_payment_is_enough_to_press() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for (fee_bps, press_fee, min_increment) in [(0, 0, 0), (1_000, 0, 0), (0, 50, 0), (333, 50, 100)] {
+                // GIVEN
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                let config = GameConfig {
+                    fee_bps,
+                    press_fee,
+                    min_increment,
+                    ..GameConfig::new(86400 * 1000, 1000)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+
+                for caller in [accounts.bob, accounts.charlie] {
+                    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                    let required = button.get_required_payment();
+
+                    // WHEN / THEN
+                    ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(required - 1);
+                    assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+                    ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(required);
+                    assert_eq!(button.press(Balance::MAX), Ok(()));
+                }
+            }
+        }
+
+        #[ink::test]
+        fn banned_accounts_follow_ban_and_unban() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+
+            // WHEN
+            for account in [accounts.bob, accounts.charlie, accounts.django, accounts.bob] {
+                assert_eq!(button.ban(account), Ok(()));
+            }
+
+            // THEN
+            assert_eq!(button.get_banned_accounts(), vec![accounts.bob, accounts.charlie, accounts.django]);
+
+            // WHEN
+            assert_eq!(button.unban(accounts.charlie), Ok(()));
+            assert_eq!(button.unban(accounts.eve), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_banned_accounts(), vec![accounts.bob, accounts.django]);
+            assert!(!button.is_banned(accounts.charlie));
+            assert_eq!(button.ban(accounts.charlie), Ok(()));
+            assert_eq!(button.get_banned_accounts(), vec![accounts.bob, accounts.django, accounts.charlie]);
+        }
+
+        #[ink::test]
+        fn ban_rejects_too_many_accounts() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            let banned = |index: usize| {
+                let mut account = [0x01; 32];
+                account[..8].copy_from_slice(&(index as u64).to_le_bytes());
+                AccountId::from(account)
+            };
+            for index in 0..MAX_BANNED_ACCOUNTS {
+                assert_eq!(button.ban(banned(index)), Ok(()));
+            }
+
+            // WHEN / THEN
+            assert_eq!(button.ban(accounts.bob), Err(Error::TooManyBannedAccounts));
+            assert!(!button.is_banned(accounts.bob));
+            // banning an account again does not need a free slot
+            assert_eq!(button.ban(banned(0)), Ok(()));
+            assert_eq!(button.get_banned_accounts().len(), MAX_BANNED_ACCOUNTS);
+
+            // unbanning frees a slot
+            assert_eq!(button.unban(banned(0)), Ok(()));
+            assert_eq!(button.ban(accounts.bob), Ok(()));
+        }
+
+        #[ink::test]
+        fn advance_last_press_allows_payout_without_waiting() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+            assert_eq!(button.advance_last_press(86400 * 1000), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.advance_last_press(86400 * 1000), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_countdown(), 0);
+            let bob_balance = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(button.payout(), Ok(()));
+            let bob_balance_after = ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob).unwrap();
+            assert_eq!(bob_balance_after - bob_balance, 1000);
+        }
+
+        #[ink::test]
+        fn error_codes_are_fixed() {
+            assert_eq!(Error::CountdownNotPassed.to_code(), 1);
+            assert_eq!(Error::InsertCoinToContinue.to_code(), 2);
+            assert_eq!(Error::InvalidConfig.to_code(), 3);
+            assert_eq!(Error::NotOwner.to_code(), 4);
+            assert_eq!(Error::GameAlreadyPlayed.to_code(), 5);
+            assert_eq!(Error::ThemeTooLong.to_code(), 6);
+            assert_eq!(Error::NotApprover.to_code(), 7);
+            assert_eq!(Error::AlreadyApproved.to_code(), 8);
+            assert_eq!(Error::NotEnoughApprovals.to_code(), 9);
+            assert_eq!(Error::NotEnoughPlayers.to_code(), 10);
+            assert_eq!(Error::TooManyAccounts.to_code(), 11);
+            assert_eq!(Error::HoldTooShort.to_code(), 12);
+            assert_eq!(Error::SelfPressCooldown.to_code(), 13);
+            assert_eq!(Error::RoundOver.to_code(), 14);
+            assert_eq!(Error::NotWinner.to_code(), 15);
+            assert_eq!(Error::InvalidSplit.to_code(), 16);
+            assert_eq!(Error::NotWhitelisted.to_code(), 17);
+            assert_eq!(Error::Banned.to_code(), 18);
+            assert_eq!(Error::PriceChanged.to_code(), 19);
+            assert_eq!(Error::MessageTooLong.to_code(), 20);
+            assert_eq!(Error::AlreadyPressedThisBlock.to_code(), 21);
+            assert_eq!(Error::PotTooSmall.to_code(), 22);
+            assert_eq!(Error::NotRelayer.to_code(), 23);
+            assert_eq!(Error::NotCurrentLeader.to_code(), 24);
+            assert_eq!(Error::GameExpired.to_code(), 25);
+            assert_eq!(Error::NothingToWithdraw.to_code(), 26);
+            assert_eq!(Error::PressTooSoon.to_code(), 27);
+            assert_eq!(Error::NotAuthorizedToPayout.to_code(), 28);
+            assert_eq!(Error::GameNotFound.to_code(), 29);
+            assert_eq!(Error::TooManyGames.to_code(), 30);
+            assert_eq!(Error::GameNotStarted.to_code(), 31);
+            assert_eq!(Error::OverMaxRaise.to_code(), 32);
+            assert_eq!(Error::RevealRequired.to_code(), 33);
+            assert_eq!(Error::NoCommitment.to_code(), 34);
+            assert_eq!(Error::InvalidReveal.to_code(), 35);
+            assert_eq!(Error::WinnerCooldown.to_code(), 36);
+            assert_eq!(Error::StaleQuote.to_code(), 37);
+            assert_eq!(Error::NothingToPayout.to_code(), 38);
+            assert_eq!(Error::PressLimitReached.to_code(), 39);
+            assert_eq!(Error::CountdownFrozen.to_code(), 40);
+            assert_eq!(Error::CountdownNotFrozen.to_code(), 41);
+            assert_eq!(Error::TransferFailed.to_code(), 42);
+            assert_eq!(Error::UnclaimedWinnings.to_code(), 43);
+            assert_eq!(Error::TooManyBannedAccounts.to_code(), 44);
+            assert_eq!(Error::NoLeader.to_code(), 45);
+            assert_eq!(Error::NotPerpetual.to_code(), 46);
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let button = TheButton::new(86400 * 1000, 1000);
+            assert_eq!(button.error_code(Error::OverMaxRaise), 32);
+        }
+
+        #[ink::test]
+        fn price_projection_matches_real_presses() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                min_increment: 100,
+                max_price: 1250,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            let projected: Vec<Balance> = (0..5).map(|presses| button.get_price_after(presses)).collect();
+            assert_eq!(projected, vec![1000, 1100, 1200, 1250, 1250]);
+
+            // WHEN / THEN
+            for (index, expected) in projected.into_iter().enumerate() {
+                assert_eq!(button.get_current_price(), expected);
+                let caller = if index % 2 == 0 { accounts.bob } else { accounts.charlie };
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(expected);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+            assert_eq!(button.get_price_after(u32::MAX), 1250);
+        }
+
+        #[ink::test]
+        fn commit_reveal_presses_with_matching_nonce() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                commit_reveal: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Err(Error::RevealRequired));
+            assert_eq!(button.reveal(42, Balance::MAX), Err(Error::NoCommitment));
+
+            // WHEN
+            button.commit(button.get_commitment_hash(accounts.bob, 42));
+
+            // THEN
+            assert_eq!(button.reveal(42, Balance::MAX), Ok(()));
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+
+            // the commitment is used up
+            assert_eq!(button.reveal(42, Balance::MAX), Err(Error::NoCommitment));
+        }
+
+        #[ink::test]
+        fn commit_reveal_rejects_mismatched_nonce() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                commit_reveal: true,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            button.commit(button.get_commitment_hash(accounts.bob, 42));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+
+            // WHEN / THEN
+            assert_eq!(button.reveal(43, Balance::MAX), Err(Error::InvalidReveal));
+
+            // another account cannot reveal bob's commitment
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            button.commit(button.get_commitment_hash(accounts.bob, 42));
+            assert_eq!(button.reveal(42, Balance::MAX), Err(Error::InvalidReveal));
+            assert_eq!(button.get_last_press_caller(), accounts.alice);
+        }
+
+        #[ink::test]
+        fn status_follows_game_lifecycle() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                start_time: 1000,
+                ..GameConfig::new(10000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+
+            // WHEN / THEN
+            assert_eq!(button.get_status(), GameStatus::NotStarted);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.get_status(), GameStatus::Active);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10999);
+            assert_eq!(button.get_status(), GameStatus::Active);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(11000);
+            assert_eq!(button.get_status(), GameStatus::PayoutReady);
+        }
+
+        #[ink::test]
+        fn status_is_ended_once_expired_game_cannot_pay_out() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                max_lifetime: 5000,
+                min_unique_players: 1,
+                ..GameConfig::new(10000, 1000)
+            };
+            let button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_status(), GameStatus::Active);
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+
+            // THEN
+            assert_eq!(button.get_status(), GameStatus::Ended);
+        }
+
+        #[ink::test]
+        fn winner_cannot_reenter_during_cooldown() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                winner_reentry_cooldown: 5000,
+                ..GameConfig::new(1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.payout(), Ok(()));
+
+            // WHEN / THEN the winner has to wait
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5999);
+            assert_eq!(button.press(Balance::MAX), Err(Error::WinnerCooldown));
+
+            // other accounts are unaffected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // the winner can press once the cooldown has passed
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(6000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+        }
+
+        #[ink::test]
+        fn restore_initializes_from_snapshot() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            // WHEN
+            let button = TheButton::restore(
+                86400 * 1000,
+                1000,
+                42,
+                123_000,
+                vec![(accounts.bob, 50_000), (accounts.charlie, 70_000)],
+            )
+            .unwrap();
+
+            // THEN
+            assert_eq!(button.get_active_countdown_duration(), 86400 * 1000);
+            assert_eq!(button.min_next_press(), 1000);
+            assert_eq!(button.get_press_count(), 42);
+            assert_eq!(button.get_total_volume(), 123_000);
+            assert_eq!(button.get_total_won(accounts.bob), 50_000);
+            assert_eq!(button.get_total_won(accounts.charlie), 70_000);
+            assert_eq!(button.get_total_won(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn restore_rejects_malformed_snapshot() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+
+            let duplicate = vec![(accounts.bob, 1), (accounts.bob, 2)];
+            assert_eq!(TheButton::restore(1000, 1000, 0, 0, duplicate).err(), Some(Error::InvalidConfig));
+            let too_many = vec![(accounts.bob, 1); MAX_BATCH_ACCOUNTS + 1];
+            assert_eq!(TheButton::restore(1000, 1000, 0, 0, too_many).err(), Some(Error::TooManyAccounts));
+        }
+
+        #[ink::test]
+        fn effective_min_raise_is_enforced_by_press() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            for (min_increment, max_price) in [(0, 0), (300, 0), (300, 1200), (1000, 1500)] {
+                // GIVEN
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+                let config = GameConfig {
+                    min_increment,
+                    max_price,
+                    ..GameConfig::new(86400 * 1000, 1000)
+                };
+                let mut button = TheButton::with_config(config).unwrap();
+
+                for caller in [accounts.bob, accounts.charlie, accounts.django] {
+                    ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                    let min_raise = button.get_effective_min_raise();
+                    assert_eq!(min_raise, button.min_next_press());
+
+                    // WHEN / THEN
+                    ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(min_raise - 1);
+                    assert_eq!(button.press(Balance::MAX), Err(Error::InsertCoinToContinue));
+                    ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(min_raise);
+                    assert_eq!(button.press(Balance::MAX), Ok(()));
+                }
+            }
+        }
+
+        #[ink::test]
+        fn payout_pays_bounty_to_caller() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.eve, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                payout_bounty_bps: 100,
+                ..GameConfig::new(1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1_000_000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(button.payout(), Ok(()));
+
+            // THEN
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.eve), Ok(10_000));
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(990_000));
+        }
+
+        #[ink::test]
+        fn with_config_rejects_excessive_bounty() {
+            let config = GameConfig {
+                charity: Some(AccountId::from([0x01; 32])),
+                charity_bps: 6_000,
+                payout_bounty_bps: 4_001,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
+
+        #[ink::test]
+        fn press_cost_estimate_tracks_price_and_gas_hint() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let config = GameConfig {
+                min_increment: 100,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            assert_eq!(button.get_press_cost_estimate(), (1000, 0));
+
+            // WHEN
+            assert_eq!(button.set_gas_hint(5_000_000), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(button.set_gas_hint(1), Err(Error::NotOwner));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_press_cost_estimate(), (1100, 5_000_000));
+        }
+
+        #[ink::test]
+        fn reset_stats_keeps_live_round() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                leaderboard_size: 3,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            for (index, caller) in [accounts.bob, accounts.charlie, accounts.bob].into_iter().enumerate() {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(index as u64 * 1000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
+            }
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            let countdown = button.get_countdown();
+            assert_eq!(button.reset_stats(), Err(Error::NotOwner));
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(button.reset_stats(), Ok(()));
+
+            // THEN
+            assert_eq!(button.get_press_count(), 0);
+            assert_eq!(button.get_total_volume(), 0);
+            assert_eq!(button.get_leaderboard(), vec![]);
+            assert_eq!(button.get_presses(accounts.bob), 0);
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+            assert_eq!(button.get_last_press_timestamp(), 2000);
+            assert_eq!(button.get_countdown(), countdown);
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = <StatsReset as ink::scale::Decode>::decode(&mut &emitted_events[6].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.season, 1);
+
+            // the new season counts from zero
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_press_count(), 1);
+            assert_eq!(button.get_presses(accounts.charlie), 1);
+            assert_eq!(button.get_leaderboard(), vec![(accounts.charlie, 1)]);
+        }
+
+        #[ink::test]
+        fn tip_goes_to_leader() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            let countdown = button.get_countdown();
+
+            // WHEN
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(button.tip_leader(), Ok(()));
+
+            // THEN
+            assert_eq!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob), Ok(500));
+            assert_eq!(button.get_balance(), 1000);
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+            assert_eq!(button.get_last_press_timestamp(), 0);
+            assert_eq!(button.get_countdown(), countdown);
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            let event = <LeaderTipped as ink::scale::Decode>::decode(&mut &emitted_events[2].data[..])
+                .expect("invalid event data");
+            assert_eq!(event.tipper, accounts.charlie);
+            assert_eq!(event.leader, accounts.bob);
+            assert_eq!(event.amount, 500);
+        }
+
+        #[ink::test]
+        fn tip_requires_value_and_leader() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let mut button = TheButton::new(86400 * 1000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+
+            // WHEN / THEN
+            // nobody has pressed yet, so the owner would receive the tip
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(500);
+            assert_eq!(button.tip_leader(), Err(Error::NoLeader));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert_eq!(button.tip_leader(), Err(Error::InsertCoinToContinue));
+        }
+
+        #[ink::test]
+        fn payout_of_zero_balance_in_perpetual_mode_is_rejected() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+
+            // THEN the round is not reset for free
+            assert_eq!(button.payout(), Err(Error::NothingToPayout));
+            assert_eq!(button.get_round(), 1);
+            assert_eq!(button.get_last_press_caller(), accounts.bob);
+        }
+
+        #[ink::test]
+        fn payout_of_existential_deposit_in_perpetual_mode_is_rejected() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 1_000_000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                existential_deposit: 1_000_000,
+                ..GameConfig::new(1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
 
-    impl TheButton {
-        /// The constructor initializes the contract countdown duration in milliseconds.
-        /// The contract caller and timestamp are set to the caller and the block timestamp.
-        #[ink(constructor)]
-        pub fn new(countdown_duration: u64, min_raise_balance: Balance) -> Self {
-            let last_press_caller = Self::env().caller();
-            let last_press_timestamp = Self::env().block_timestamp();
+            // WHEN
+            // nobody presses and the countdown passes
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
 
-            Self {
-                last_press_caller,
-                last_press_timestamp,
-                countdown_duration,
-                min_raise_balance,
-            }
+            // THEN only the reserved existential deposit is left, so the round is not reset for free
+            assert_eq!(button.payout_proof().reserved, 1_000_000);
+            assert_eq!(button.payout(), Err(Error::NothingToPayout));
+            assert_eq!(button.get_round(), 1);
+            assert_eq!(button.get_balance(), 1_000_000);
         }
 
-        /// The default constructor initializes the contract with a countdown duration of 24 hours
-        /// and a minimum raised balance of 1e10 units. (1 PAS, 1 DOT, 0.01 KSM)
-        #[ink(constructor)]
-        pub fn default() -> Self {
-            Self::new(86400 * 1000, 10_000_000_000)
-        }
-        
-        /// The caller has to pay at least 1 unit of balance to press the button.
-        /// The last caller and timestamp are updated. This resets the countdown.
-        /// If the caller has not paid enough balance, the error `InsertCoinToContinue` is returned.
-        #[ink(message, payable)]
-        pub fn press(&mut self) -> Result<()> {
-            // ensure that the caller has paid at least 1 unit of balance
-            let _transferred = self.env().transferred_value();
-            if _transferred < self.min_raise_balance {
-                return Err(Error::InsertCoinToContinue);
-            }
+        #[ink::test]
+        fn payout_of_zero_balance_terminates() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(1000, 1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
 
-            self.last_press_caller = self.env().caller();
-            self.last_press_timestamp = self.env().block_timestamp();
+            // WHEN
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
 
-            Ok(())
+            // THEN nothing is transferred and the contract is terminated
+            let should_terminate = move || button.payout().unwrap();
+            ink::env::test::assert_contract_termination::<ink::env::DefaultEnvironment, _>(
+                should_terminate,
+                accounts.bob,
+                0,
+            );
         }
 
-        /// Claims the reward if 24 hours have passed since the last press.
-        /// The balance of the contract is transferred to the last user who pressed the button.
-        /// If the countdown has not passed yet, the error `CountdownNotPassed` is returned.
-        /// The contract is terminated after the reward is paid out. Any remaining balance is sent to the caller.
-        #[ink(message)]
-        pub fn payout(&mut self) -> Result<()> {
-            let now = self.env().block_timestamp();
-            let last_call = self.last_press_timestamp;
-            let time_passed = now.checked_sub(last_call).unwrap();
+        #[ink::test]
+        fn press_limit_ends_round_early() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                max_presses_per_round: 2,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
 
-            if time_passed < self.countdown_duration {
-                return Err(Error::CountdownNotPassed);
+            // WHEN
+            for caller in [accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+                ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+                assert_eq!(button.press(Balance::MAX), Ok(()));
             }
 
-            // transfer the balance to the caller
-            let balance = self.env().balance();
-            let _result = self.env().transfer(self.last_press_caller, balance);
+            // THEN further presses are rejected and the round can be paid out right away
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Err(Error::PressLimitReached));
+            assert!(button.get_countdown() > 0);
+            assert_eq!(button.payout(), Ok(()));
 
-            self.env().terminate_contract(self.env().caller());
+            // the next round starts with a fresh limit
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1_000_000);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
         }
 
-        /// Return the countdown until the next payout
-        #[ink(message)]
-        pub fn get_countdown(&self) -> u64 {
-            let now = self.env().block_timestamp();
-            let last_call = self.last_press_timestamp;
-            let time_passed = now.checked_sub(last_call).unwrap();
+        #[ink::test]
+        fn get_participation_reports_account_involvement() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let config = GameConfig {
+                perpetual: true,
+                ..GameConfig::new(86400 * 1000, 100)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
 
-            if time_passed >= self.countdown_duration {
-                return 0;
-            }
+            // WHEN
+            // bob wins the first round and charlie leads the second one
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(300);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(86400 * 1000);
+            assert_eq!(button.payout(), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(200);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
 
-            self.countdown_duration.checked_sub(time_passed).unwrap()
+            // THEN
+            let expected = |presses, contributed, won, holds_button| Participation {
+                presses,
+                contributed,
+                won,
+                holds_button,
+            };
+            assert_eq!(button.get_participation(accounts.bob), expected(1, 300, 300, false));
+            assert_eq!(button.get_participation(accounts.charlie), expected(1, 200, 0, true));
+            assert_eq!(button.get_participation(accounts.django), expected(0, 0, 0, false));
         }
 
-        /// Return the account id of the last caller
-        #[ink(message)]
-        pub fn get_last_press_caller(&self) -> AccountId {
-            self.last_press_caller
-        }
+        #[ink::test]
+        fn frozen_countdown_moves_the_deadline() {
+            // GIVEN
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = AccountId::from([0xFF; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            let mut button = TheButton::new(1000, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::transfer_in::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(button.press(Balance::MAX), Ok(()));
+            assert_eq!(button.get_effective_deadline(), 1000);
 
-        /// Return the timestamp of the last call
-        #[ink(message)]
-        pub fn get_last_press_timestamp(&self) -> u64 {
-            self.last_press_timestamp
-        }
+            // WHEN
+            // the owner freezes the countdown for 5000 ms after 400 ms have passed
+            assert_eq!(button.freeze_countdown(), Err(Error::NotOwner));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(400);
+            assert_eq!(button.freeze_countdown(), Ok(()));
+            assert_eq!(button.freeze_countdown(), Err(Error::CountdownFrozen));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5400);
+            assert_eq!(button.get_status(), GameStatus::Paused);
+            assert_eq!(button.get_countdown(), 600);
+            assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+            assert_eq!(button.resume_countdown(), Ok(()));
+            assert_eq!(button.resume_countdown(), Err(Error::CountdownNotFrozen));
 
-        /// Returns the contract balance. This is a convenience function to show the contract balance
-        /// in contract explorers.
-        #[ink(message)]
-        pub fn get_balance(&self) -> Balance {
-            self.env().balance()
+            // THEN
+            // the deadline moved by the frozen duration
+            assert_eq!(button.get_status(), GameStatus::Active);
+            assert_eq!(button.get_effective_deadline(), 6000);
+            assert_eq!(button.get_countdown(), 600);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5999);
+            assert_eq!(button.payout(), Err(Error::CountdownNotPassed));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(6000);
+            assert_eq!(button.get_countdown(), 0);
+            assert_eq!(button.get_status(), GameStatus::PayoutReady);
         }
-    }
-
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        #[ink::test]
+        fn with_config_rejects_full_fee() {
+            let config = GameConfig {
+                fee_bps: 10_000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
+        }
 
         #[ink::test]
-        fn default_works() {
-            // set up simulated environment
+        fn with_config_rejects_oracle_without_quote_age() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let caller = accounts.alice;
-            let block_timestamp = 12345;
-
-            // Set the caller and block timestamp
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp);
-
-            // Initialize the contract
-            let button = TheButton::new(86400 * 1000, 1000);
-
-            // Check that the contract was initialized correctly
-            assert_eq!(button.get_last_press_timestamp(), block_timestamp);
-            assert_eq!(button.get_last_press_caller(), caller);
+            let config = GameConfig {
+                price_oracle: Some(accounts.django),
+                reference_min_raise: 10,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            assert_eq!(TheButton::with_config(config).err(), Some(Error::InvalidConfig));
         }
 
         #[ink::test]
-        fn press_works() {
+        fn oracle_minimum_is_capped_at_max_price() {
             // GIVEN
-
-            // set up simulated environment
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let block_timestamp = 0;
-            // Set the caller and block timestamp
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp);
-
-            // Initialize the contract
-            let mut button = TheButton::new(86400 * 1000, 1000);
+            let config = GameConfig {
+                price_oracle: Some(accounts.django),
+                reference_min_raise: 10,
+                max_quote_age: 60 * 1000,
+                max_price: 5000,
+                ..GameConfig::new(86400 * 1000, 1000)
+            };
+            let mut button = TheButton::with_config(config).unwrap();
 
             // WHEN
-            // Set a new caller and block timestamp
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(block_timestamp + 1000);
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
-
-            // Press the button
-            let result = button.press();
+            // the oracle quotes a rate that converts the reference minimum to 10_000
+            button.oracle_rate = 1000;
 
             // THEN
-            // Check that the button was pressed successfully
-            assert_eq!(result, Ok(()));
-            assert_eq!(button.get_last_press_caller(), accounts.bob);
-            assert_eq!(button.get_last_press_timestamp(), block_timestamp + 1000);
+            assert_eq!(button.min_next_press(), 5000);
+            assert!(button.check_invariants());
         }
 /*
         #[ink::test]
@@ -217,7 +6942,7 @@ mod the_button {
             ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
 
             // Press the button
-            let _result = button.press();
+            let _result = button.press(Balance::MAX);
 
             // Set a new caller and block timestamp
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
@@ -236,10 +6961,14 @@ mod the_button {
     mod e2e_tests {
         /// Imports all the definitions from the outer scope so we can use them here.
         use super::*;
+        use ink_e2e::{ChainBackend, ContractsBackend};
+        use mock_oracle::{MockOracle, MockOracleRef};
+        use mock_payout_receiver::{MockPayoutReceiver, MockPayoutReceiverRef};
+        use mock_router::{MockRouter, MockRouterRef};
 
         const COUNTDOWN_DURATION: u64 = 86400 * 1000;
         const MIN_RAISE_BALANCE: Balance = 1000;
-        
+
         /// The End-to-End test `Result` type.
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -247,7 +6976,7 @@ mod the_button {
         #[ink_e2e::test]
         async fn contract_creation_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
             // Given
-            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION);
+            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE);
 
             // When
             let contract = client
@@ -260,14 +6989,17 @@ mod the_button {
             // Then
             let get_countdown = call_builder.get_countdown();
             let get_countdown_result = client.call(&ink_e2e::alice(), &get_countdown).dry_run().await?;
-            assert!(matches!(get_countdown_result.return_value(), COUNTDOWN_DURATION));
+            assert_eq!(get_countdown_result.return_value(), COUNTDOWN_DURATION);
 
             let get_last_press_caller = call_builder.get_last_press_caller();
-            let get_last_press_caller_result = client.call(&ink_e2e::alice(), &get_last_press_caller).await?;
-            assert_eq!(get_last_press_caller_result.return_value(), ink_e2e::alice().account_id);
+            let get_last_press_caller_result = client.call(&ink_e2e::alice(), &get_last_press_caller).dry_run().await?;
+            assert_eq!(
+                get_last_press_caller_result.return_value(),
+                ink_e2e::account_id(ink_e2e::AccountKeyring::Alice)
+            );
 
             let get_last_press_timestamp = call_builder.get_last_press_timestamp();
-            let get_last_press_timestamp_result = client.call(&ink_e2e::alice(), &get_last_press_timestamp).await?;
+            let get_last_press_timestamp_result = client.call(&ink_e2e::alice(), &get_last_press_timestamp).dry_run().await?;
             assert!(get_last_press_timestamp_result.return_value() > 0);
 
             Ok(())
@@ -283,80 +7015,342 @@ mod the_button {
                 .submit()
                 .await
                 .expect("instantiate failed");
-            let call_builder = contract.call_builder::<TheButton>();
+            let mut call_builder = contract.call_builder::<TheButton>();
 
             // When
-            let press = call_builder.press().transferred_value(MIN_RAISE_BALANCE);
-            let _result = client.call(&ink_e2e::bob(), &press).await?;
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
 
             // Then
             let get_last_press_caller = call_builder.get_last_press_caller();
-            let get_last_press_caller_result = client.call(&ink_e2e::alice(), &get_last_press_caller).await?;
-            assert_eq!(get_last_press_caller_result.return_value(), ink_e2e::bob().account_id);
+            let get_last_press_caller_result = client.call(&ink_e2e::alice(), &get_last_press_caller).dry_run().await?;
+            assert_eq!(
+                get_last_press_caller_result.return_value(),
+                ink_e2e::account_id(ink_e2e::AccountKeyring::Bob)
+            );
 
             let get_countdown = call_builder.get_countdown();
-            let get_countdown_result = client.call(&ink_e2e::alice(), &get_countdown).await?;
+            let get_countdown_result = client.call(&ink_e2e::alice(), &get_countdown).dry_run().await?;
             assert_eq!(get_countdown_result.return_value(), COUNTDOWN_DURATION);
-            
+
             Ok(())
         }
 
-        /*
         #[ink_e2e::test]
-        async fn payout_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+        async fn press_requires_oracle_minimum(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
             // Given
+            let mut oracle_constructor = MockOracleRef::new(10);
+            let oracle = client
+                .instantiate("mock_oracle", &ink_e2e::alice(), &mut oracle_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let config = GameConfig {
+                price_oracle: Some(oracle.account_id),
+                reference_min_raise: 200,
+                max_quote_age: 60 * 1000,
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
 
-            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE);
+            // When
+            let press = call_builder.press(Balance::MAX);
+            let too_little_result = client.call(&ink_e2e::bob(), &press).value(1999).dry_run().await?;
+            let _result = client.call(&ink_e2e::bob(), &press).value(2000).submit().await?;
+
+            // Then
+            assert_eq!(too_little_result.return_value(), Err(Error::InsertCoinToContinue));
+
+            let get_oracle_rate = call_builder.get_oracle_rate();
+            let get_oracle_rate_result = client.call(&ink_e2e::alice(), &get_oracle_rate).dry_run().await?;
+            assert_eq!(get_oracle_rate_result.return_value().0, 10);
+
+            let get_last_press_caller = call_builder.get_last_press_caller();
+            let get_last_press_caller_result = client.call(&ink_e2e::alice(), &get_last_press_caller).dry_run().await?;
+            assert_eq!(
+                get_last_press_caller_result.return_value(),
+                ink_e2e::account_id(ink_e2e::AccountKeyring::Bob)
+            );
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn press_rejects_stale_oracle_quote(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut oracle_constructor = MockOracleRef::new(10);
+            let oracle = client
+                .instantiate("mock_oracle", &ink_e2e::alice(), &mut oracle_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut oracle_call_builder = oracle.call_builder::<MockOracle>();
+            let config = GameConfig {
+                price_oracle: Some(oracle.account_id),
+                reference_min_raise: 200,
+                max_quote_age: 60 * 1000,
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
             let contract = client
                 .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
                 .submit()
                 .await
                 .expect("instantiate failed");
-            let call_builder = contract.call_builder::<TheButton>();
+            let mut call_builder = contract.call_builder::<TheButton>();
+
+            // When
+            let set_rate = oracle_call_builder.set_rate(10, 0);
+            let _result = client.call(&ink_e2e::alice(), &set_rate).submit().await?;
+
+            // Then
+            let press = call_builder.press(Balance::MAX);
+            let press_result = client.call(&ink_e2e::bob(), &press).value(2000).dry_run().await?;
+            assert_eq!(press_result.return_value(), Err(Error::StaleQuote));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn payout_is_swapped_through_router(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut router_constructor = MockRouterRef::new(false);
+            let router = client
+                .instantiate("mock_router", &ink_e2e::alice(), &mut router_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let router_call_builder = router.call_builder::<MockRouter>();
+            let config = GameConfig {
+                perpetual: true,
+                // the round can be paid out right after bob's press
+                max_presses_per_round: 1,
+                stable_router: Some(router.account_id),
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
+
+            // bob opts into stable payouts and wins the round
+            let set_payout_preference = call_builder.set_payout_preference(true);
+            let _result = client.call(&ink_e2e::bob(), &set_payout_preference).submit().await?;
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
+
+            // When
+            let simulate_payout = call_builder.simulate_payout();
+            let simulate_payout_result = client.call(&ink_e2e::alice(), &simulate_payout).dry_run().await?;
+            let (winner, amount) = simulate_payout_result.return_value().expect("payout not allowed");
+            let payout = call_builder.payout();
+            let _result = client.call(&ink_e2e::alice(), &payout).submit().await?;
+
+            // Then
+            assert_eq!(winner, ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            let get_last_swap = router_call_builder.get_last_swap();
+            let get_last_swap_result = client.call(&ink_e2e::alice(), &get_last_swap).dry_run().await?;
+            assert_eq!(get_last_swap_result.return_value(), Some((winner, amount)));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn payout_falls_back_to_native_when_swap_fails(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut router_constructor = MockRouterRef::new(true);
+            let router = client
+                .instantiate("mock_router", &ink_e2e::alice(), &mut router_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let router_call_builder = router.call_builder::<MockRouter>();
+            let config = GameConfig {
+                perpetual: true,
+                // the round can be paid out right after bob's press
+                max_presses_per_round: 1,
+                stable_router: Some(router.account_id),
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
+
+            // bob opts into stable payouts and wins the round
+            let set_payout_preference = call_builder.set_payout_preference(true);
+            let _result = client.call(&ink_e2e::bob(), &set_payout_preference).submit().await?;
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            // When
+            let simulate_payout = call_builder.simulate_payout();
+            let simulate_payout_result = client.call(&ink_e2e::alice(), &simulate_payout).dry_run().await?;
+            let (_winner, amount) = simulate_payout_result.return_value().expect("payout not allowed");
+            let bob_balance = client.free_balance(bob).await?;
+            let payout = call_builder.payout();
+            let _result = client.call(&ink_e2e::alice(), &payout).submit().await?;
+
+            // Then
+            let get_last_swap = router_call_builder.get_last_swap();
+            let get_last_swap_result = client.call(&ink_e2e::alice(), &get_last_swap).dry_run().await?;
+            assert_eq!(get_last_swap_result.return_value(), None);
+            assert_eq!(client.free_balance(bob).await?, bob_balance + amount);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn payout_notifies_callback(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut receiver_constructor = MockPayoutReceiverRef::new(false);
+            let receiver = client
+                .instantiate("mock_payout_receiver", &ink_e2e::alice(), &mut receiver_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let receiver_call_builder = receiver.call_builder::<MockPayoutReceiver>();
+            let config = GameConfig {
+                perpetual: true,
+                // the round can be paid out right after bob's press
+                max_presses_per_round: 1,
+                on_payout_callback: Some(receiver.account_id),
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
 
             // When
-            let press = call_builder.press().transferred_value(MIN_RAISE_BALANCE);
-            let _result = client.call(&ink_e2e::alice(), &press).await?;            
-            client.advance_block().await?;
+            let simulate_payout = call_builder.simulate_payout();
+            let simulate_payout_result = client.call(&ink_e2e::alice(), &simulate_payout).dry_run().await?;
+            let (winner, amount) = simulate_payout_result.return_value().expect("payout not allowed");
+            let payout = call_builder.payout();
+            let _result = client.call(&ink_e2e::alice(), &payout).submit().await?;
+
+            // Then
+            let get_last_payout = receiver_call_builder.get_last_payout();
+            let get_last_payout_result = client.call(&ink_e2e::alice(), &get_last_payout).dry_run().await?;
+            assert_eq!(get_last_payout_result.return_value(), Some((winner, amount)));
 
-            let bobs_start_balance = client.get_balance(&ink_e2e::bob()).await?;
+            Ok(())
+        }
 
-            _result = client.call(&ink_e2e::bob(), &press).await?;
-            client.advance_block().await?;
+        #[ink_e2e::test]
+        async fn payout_survives_callback_burning_gas(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut receiver_constructor = MockPayoutReceiverRef::new(true);
+            let receiver = client
+                .instantiate("mock_payout_receiver", &ink_e2e::alice(), &mut receiver_constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let receiver_call_builder = receiver.call_builder::<MockPayoutReceiver>();
+            let config = GameConfig {
+                perpetual: true,
+                // the round can be paid out right after bob's press
+                max_presses_per_round: 1,
+                on_payout_callback: Some(receiver.account_id),
+                ..GameConfig::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE)
+            };
+            let mut constructor = TheButtonRef::with_config(config);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
 
-            let bobs_balance_after_press = client.get_balance(&ink_e2e::bob()).await?;
-            
+            // When
             let payout = call_builder.payout();
-            let result = client.call(&ink_e2e::bob(), &payout).await?;
+            let payout_result = client.call(&ink_e2e::alice(), &payout).submit().await?;
+
+            // Then
+            assert_eq!(payout_result.return_value(), Ok(()));
+            let get_round = call_builder.get_round();
+            let get_round_result = client.call(&ink_e2e::alice(), &get_round).dry_run().await?;
+            assert_eq!(get_round_result.return_value(), 2);
+            let get_last_payout = receiver_call_builder.get_last_payout();
+            let get_last_payout_result = client.call(&ink_e2e::alice(), &get_last_payout).dry_run().await?;
+            assert_eq!(get_last_payout_result.return_value(), None);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn payout_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Given
+            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE);
+            let contract = client
+                .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<TheButton>();
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
 
-            let bobs_balance_after_payout = client.get_balance(&ink_e2e::bob()).await?;
+            // When
+            // the owner ages the game instead of waiting for the countdown
+            let advance_last_press = call_builder.advance_last_press(COUNTDOWN_DURATION);
+            let _result = client.call(&ink_e2e::alice(), &advance_last_press).submit().await?;
+            let simulate_payout = call_builder.simulate_payout();
+            let simulated = client.call(&ink_e2e::alice(), &simulate_payout).dry_run().await?.return_value();
+            let bob_balance = client.free_balance(bob).await?;
+            let payout = call_builder.payout();
+            let _result = client.call(&ink_e2e::alice(), &payout).submit().await?;
 
             // Then
-            // the contract should have terminated
-            contract.assert_terminated();
-            assert_eq!(bobs_balance_after_press, bobs_start_balance - MIN_RAISE_BALANCE);
-            assert_eq!(bobs_balance_after_payout, bobs_balance_after_press + contract_balance);
-            
+            // bob receives at least the simulated prize and the contract is terminated
+            let (winner, winner_amount) = simulated.expect("payout should be allowed");
+            assert_eq!(winner, bob);
+            assert!(client.free_balance(bob).await? >= bob_balance + winner_amount);
+            let get_balance = call_builder.get_balance();
+            let get_balance_result = client.call(&ink_e2e::alice(), &get_balance).dry_run().await;
+            assert!(get_balance_result.map_or(true, |result| result.is_err()));
+
             Ok(())
         }
 
         #[ink_e2e::test]
         async fn press_fails_without_payment(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
             // Given
-            let mut constructor = TheButtonRef::new(86400 * 1000);
+            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE);
             let contract = client
                 .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
                 .submit()
                 .await
                 .expect("instantiate failed");
-            let call_builder = contract.call_builder::<TheButton>();
+            let mut call_builder = contract.call_builder::<TheButton>();
 
             // When
-            let press = call_builder.press();
-            let result = client.call(&ink_e2e::bob(), &press).await?;
+            let press = call_builder.press(Balance::MAX);
+            let press_result = client.call(&ink_e2e::bob(), &press).dry_run().await?;
 
             // Then
-            assert_eq!(result.return_value(), Err(Error::InsertCoinToContinue));
+            assert_eq!(press_result.return_value(), Err(Error::InsertCoinToContinue));
 
             Ok(())
         }
@@ -364,27 +7358,28 @@ mod the_button {
         #[ink_e2e::test]
         async fn payout_fails_before_countdown(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
             // Given
-            let mut constructor = TheButtonRef::new(86400 * 1000);
+            let mut constructor = TheButtonRef::new(COUNTDOWN_DURATION, MIN_RAISE_BALANCE);
             let contract = client
                 .instantiate("the_button", &ink_e2e::alice(), &mut constructor)
                 .submit()
                 .await
                 .expect("instantiate failed");
-            let call_builder = contract.call_builder::<TheButton>();
+            let mut call_builder = contract.call_builder::<TheButton>();
+            let press = call_builder.press(Balance::MAX);
+            let _result = client.call(&ink_e2e::bob(), &press).value(MIN_RAISE_BALANCE).submit().await?;
 
             // When
-            let press = call_builder.press().transferred_value(1);
-            let _result = client.call(&ink_e2e::bob(), &press).await?;
-
+            // the game is aged by less than the countdown
+            let advance_last_press = call_builder.advance_last_press(COUNTDOWN_DURATION - 1000);
+            let _result = client.call(&ink_e2e::alice(), &advance_last_press).submit().await?;
             let payout = call_builder.payout();
-            let result = client.call(&ink_e2e::bob(), &payout).await?;
+            let payout_result = client.call(&ink_e2e::bob(), &payout).dry_run().await?;
 
             // Then
-            assert_eq!(result.return_value(), Err(Error::CountdownNotPassed));
+            assert_eq!(payout_result.return_value(), Err(Error::CountdownNotPassed));
 
             Ok(())
         }
-        */
     }
 
 }